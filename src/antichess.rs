@@ -0,0 +1,97 @@
+//! Antichess, or losing chess: captures are compulsory and the aim is to
+//! lose every piece, or to be left without a move. The king is an ordinary
+//! piece that can be captured, so there is no check, no castling, and pawns
+//! may promote to a king as well.
+
+use crate::bitboard::Bitboard;
+use crate::game_state::{CastlingRights, GameState, PieceType, Player};
+use crate::movegen::generate_pseudo_legal_moves;
+use crate::moves::{Move, MoveList};
+use crate::result::{seventy_five_move_result, DrawReason, GameResult, WinReason};
+use crate::validation::{into_result, position_errors_except, PositionError};
+use crate::variant::Variant;
+
+// a8, bit 0, is a light square
+const LIGHT_SQUARES: Bitboard = 0xaa55_aa55_aa55_aa55;
+
+// only bishops left, each side's all on one colour and the two sides' on
+// different colours, so no capture can ever happen again
+fn bishops_never_meet(game_state: &GameState) -> bool {
+    if game_state.occupied() != game_state.pieces[PieceType::Bishop as usize] {
+        return false;
+    }
+    let white = game_state.colors[Player::White as usize];
+    let black = game_state.colors[Player::Black as usize];
+    (white & !LIGHT_SQUARES == 0 && black & LIGHT_SQUARES == 0) ||
+        (white & LIGHT_SQUARES == 0 && black & !LIGHT_SQUARES == 0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Antichess;
+
+impl Variant for Antichess {
+    fn name(&self) -> &'static str {
+        "antichess"
+    }
+
+    fn starting_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1"
+    }
+
+    // any number of kings, and none of them can be in check
+    fn validate(&self, game_state: &GameState) -> Result<(), Vec<PositionError>> {
+        let mut errors = position_errors_except(game_state, |err| {
+            matches!(err, PositionError::WrongKingCount { .. } |
+                          PositionError::OpponentInCheck { .. } |
+                          PositionError::CastlingWithoutPieces { .. })
+        });
+
+        if game_state.castling_rights != CastlingRights::none() {
+            errors.push(PositionError::CastlingNotAllowed);
+        }
+
+        into_result(errors)
+    }
+
+    // only captures, if there are any; kings move like any other piece
+    fn legal_moves(&self, game_state: &GameState) -> MoveList {
+        let pseudo_legal = generate_pseudo_legal_moves(game_state);
+        let must_capture = pseudo_legal.iter().any(Move::is_capture);
+
+        let mut moves = MoveList::new();
+        for mv in pseudo_legal.iter().filter(|mv| !must_capture || mv.is_capture()) {
+            moves.push(*mv);
+            if mv.promotion() == Some(PieceType::Queen) {
+                let (from, to) = (mv.origin(), mv.destination());
+                moves.push(if mv.is_capture() {
+                    Move::new_capture_promotion(from, to, PieceType::King)
+                } else {
+                    Move::new_promotion(from, to, PieceType::King)
+                });
+            }
+        }
+
+        moves
+    }
+
+    fn is_check(&self, _game_state: &GameState) -> bool {
+        false
+    }
+
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        let player = game_state.player_to_move;
+        if game_state.colors[player as usize] == 0 {
+            return Some(GameResult::Win { winner: player, reason: WinReason::AllPiecesLost });
+        }
+
+        if self.legal_moves(game_state).is_empty() {
+            return Some(GameResult::Win { winner: player, reason: WinReason::Stalemated });
+        }
+
+        if bishops_never_meet(game_state) {
+            return Some(GameResult::Draw { reason: DrawReason::InsufficientMaterial });
+        }
+
+        seventy_five_move_result(game_state)
+    }
+}
@@ -0,0 +1,167 @@
+//! Atomic: every capture sets off an explosion that removes the capturing
+//! piece, the captured one and every piece but a pawn next to them. Blowing
+//! up the enemy king wins, so kings never capture, and two kings may stand
+//! side by side, where neither can be in check.
+
+use crate::attacks::attackers_to;
+use crate::bitboard::{bit, row_col, square, Bitboard, Squares, KING_ATTACKS};
+use crate::game_state::{CastlingSide, GameState, PieceType, Player};
+use crate::magic::between;
+use crate::movegen::{castling_destinations, generate_pseudo_legal_moves, make_move};
+use crate::moves::{Move, MoveKind, MoveList};
+use crate::result::{seventy_five_move_result, DrawReason, GameResult, WinReason};
+use crate::validation::{into_result, position_errors_except, PositionError};
+use crate::variant::Variant;
+use crate::zobrist::castling_key;
+
+// whether `player`'s king would be attacked standing on `king`, with only
+// the pieces in `occupied` on the board. Next to the enemy king it never
+// is, as any capture there would take both kings with it.
+fn king_attacked(game_state: &GameState, player: Player, king: usize, occupied: Bitboard) -> bool {
+    let enemies = game_state.colors[player.opponent() as usize] & occupied;
+    let kings = game_state.pieces[PieceType::King as usize];
+    if KING_ATTACKS[king] & kings & enemies != 0 {
+        return false;
+    }
+    attackers_to(game_state, king, occupied) & enemies & !kings != 0
+}
+
+fn is_atomic_check(game_state: &GameState, player: Player) -> bool {
+    match game_state.king_square(player) {
+        Some(king) => king_attacked(game_state, player, king, game_state.occupied()),
+        None => false,
+    }
+}
+
+// removes the piece on `center` and every piece but a pawn around it,
+// along with the castling rights of exploded kings and rooks
+fn explode(game_state: &mut GameState, center: usize) {
+    let blast = bit(center) | KING_ATTACKS[center] & !game_state.pieces[PieceType::Pawn as usize];
+    for exploded in Squares(blast & game_state.occupied()) {
+        let (row, col) = row_col(exploded);
+        game_state.set_piece_at(row, col, PieceType::None, Player::None);
+    }
+
+    let old_rights = game_state.castling_rights;
+    for owner in [Player::White, Player::Black] {
+        for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+            if let Some(file) = old_rights.rook_file(owner, side) {
+                let rook = square(owner.home_row(), file);
+                if blast & bit(rook) != 0 || game_state.king_square(owner).is_none() {
+                    game_state.castling_rights.set_rook_file(owner, side, None);
+                }
+            }
+        }
+    }
+    game_state.zobrist_key ^= castling_key(old_rights) ^ castling_key(game_state.castling_rights);
+}
+
+// whether `player` could never blow up the other king: a bare king can not
+// capture, and against a bare king a single knight, bishop or rook, or two
+// knights, can not get a capture next to it
+fn cannot_win(game_state: &GameState, player: Player) -> bool {
+    let kings = game_state.pieces[PieceType::King as usize];
+    let own = game_state.colors[player as usize] & !kings;
+    let theirs = game_state.colors[player.opponent() as usize] & !kings;
+    let pieces = |piece: PieceType| game_state.pieces[piece as usize] & own;
+
+    if own == 0 {
+        return true;
+    }
+    if theirs != 0 || pieces(PieceType::Queen) | pieces(PieceType::Pawn) != 0 {
+        return false;
+    }
+    own.count_ones() == 1 || (own == pieces(PieceType::Knight) && own.count_ones() <= 2)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Atomic;
+
+impl Variant for Atomic {
+    fn name(&self) -> &'static str {
+        "atomic"
+    }
+
+    // the side that just moved may stand in what would be check in
+    // standard chess, as long as the kings touch
+    fn validate(&self, game_state: &GameState) -> Result<(), Vec<PositionError>> {
+        let mut errors = position_errors_except(game_state, |err| matches!(err, PositionError::OpponentInCheck { .. }));
+
+        let waiting = game_state.player_to_move.opponent();
+        if errors.iter().all(|err| !matches!(err, PositionError::WrongKingCount { .. })) &&
+           is_atomic_check(game_state, waiting) {
+            errors.push(PositionError::OpponentInCheck { player: waiting });
+        }
+
+        into_result(errors)
+    }
+
+    // every move is tried out: it must not blow up the mover's king, and
+    // must either blow up the other king or leave the mover out of check
+    fn legal_moves(&self, game_state: &GameState) -> MoveList {
+        let player = game_state.player_to_move;
+        let mut moves = MoveList::new();
+        // nothing is left to play once a king is gone
+        let king = match game_state.king_square(player) {
+            Some(king) if game_state.king_square(player.opponent()).is_some() => king,
+            _ => return moves,
+        };
+        let occupied = game_state.occupied();
+        let in_check = is_atomic_check(game_state, player);
+
+        for mv in generate_pseudo_legal_moves(game_state) {
+            match mv.kind() {
+                // the rook still shields the squares the king crosses; where
+                // the king lands is checked after playing the move
+                MoveKind::Castling => {
+                    let (king_to, _) = castling_destinations(mv.from(), mv.to());
+                    let crossed = between(king, square(king_to.0, king_to.1));
+                    let lifted = occupied ^ bit(king);
+                    if in_check || Squares(crossed).any(|sq| king_attacked(game_state, player, sq, lifted)) {
+                        continue;
+                    }
+                },
+                MoveKind::Capture if mv.origin() == king => continue,
+                _ => {},
+            }
+
+            let mut after = *game_state;
+            self.play(&mut after, mv);
+            if after.king_square(player).is_some() &&
+               (after.king_square(player.opponent()).is_none() || !is_atomic_check(&after, player)) {
+                moves.push(mv);
+            }
+        }
+
+        moves
+    }
+
+    fn play(&self, game_state: &mut GameState, mv: Move) {
+        make_move(game_state, mv);
+        if mv.is_capture() {
+            explode(game_state, mv.destination());
+        }
+    }
+
+    fn is_check(&self, game_state: &GameState) -> bool {
+        is_atomic_check(game_state, game_state.player_to_move)
+    }
+
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        for player in [Player::White, Player::Black] {
+            if game_state.king_square(player).is_none() {
+                return Some(GameResult::Win { winner: player.opponent(), reason: WinReason::KingExploded });
+            }
+        }
+
+        if let Some(result) = self.position_status(game_state).result() {
+            return Some(result);
+        }
+
+        if cannot_win(game_state, Player::White) && cannot_win(game_state, Player::Black) {
+            return Some(GameResult::Draw { reason: DrawReason::InsufficientMaterial });
+        }
+
+        seventy_five_move_result(game_state)
+    }
+}
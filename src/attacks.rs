@@ -0,0 +1,106 @@
+//! Who attacks what: attackers of a square, checkers, pinned pieces and
+//! x-ray attackers. Squares are given as (line, column), like `Move::from`.
+
+use crate::bitboard::{row_col, square, Bitboard, SquareSet, Squares, KING_ATTACKS, KNIGHT_ATTACKS, PAWN_ATTACKS};
+use crate::game_state::{GameState, PieceType, Player};
+use crate::magic::{between, bishop_attacks, rook_attacks};
+
+/// Pieces of either side attacking `square`, as if the board held only
+/// the pieces in `occupied`.
+pub(crate) fn attackers_to(game_state: &GameState, square: usize, occupied: Bitboard) -> Bitboard {
+    let pieces = &game_state.pieces;
+    let queens = pieces[PieceType::Queen as usize];
+    let pawns = pieces[PieceType::Pawn as usize];
+
+    // a pawn attacks the squares a pawn of the other side would attack from there
+    (PAWN_ATTACKS[Player::Black as usize][square] & pawns & game_state.colors[Player::White as usize]) |
+        (PAWN_ATTACKS[Player::White as usize][square] & pawns & game_state.colors[Player::Black as usize]) |
+        (KNIGHT_ATTACKS[square] & pieces[PieceType::Knight as usize]) |
+        (KING_ATTACKS[square] & pieces[PieceType::King as usize]) |
+        (bishop_attacks(square, occupied) & (pieces[PieceType::Bishop as usize] | queens)) |
+        (rook_attacks(square, occupied) & (pieces[PieceType::Rook as usize] | queens))
+}
+
+/// Whether any piece of `by` attacks `square`.
+pub(crate) fn is_square_attacked(game_state: &GameState, square: usize, by: Player) -> bool {
+    attackers_to(game_state, square, game_state.occupied()) & game_state.colors[by as usize] != 0
+}
+
+/// Whether `player`'s king is attacked by the other side.
+pub(crate) fn is_player_in_check(game_state: &GameState, player: Player) -> bool {
+    match game_state.king_square(player) {
+        Some(king) => is_square_attacked(game_state, king, player.opponent()),
+        None => false,
+    }
+}
+
+/// Pieces of the king's side that stand alone between `king` and an enemy
+/// slider, and so may only move along that line.
+pub(crate) fn pinned_pieces(game_state: &GameState, king: usize) -> Bitboard {
+    let (row, col) = row_col(king);
+    let owner = game_state.owner_at(row, col);
+    let own = game_state.colors[owner as usize];
+    let occupied = game_state.occupied();
+    let queens = game_state.pieces[PieceType::Queen as usize];
+
+    // enemy sliders that would attack the king on an empty board
+    let snipers = game_state.colors[owner.opponent() as usize] &
+        (rook_attacks(king, 0) & (game_state.pieces[PieceType::Rook as usize] | queens) |
+         bishop_attacks(king, 0) & (game_state.pieces[PieceType::Bishop as usize] | queens));
+
+    let mut pinned = 0;
+    for sniper in Squares(snipers) {
+        let blockers = between(king, sniper) & occupied;
+        if blockers.count_ones() == 1 {
+            pinned |= blockers & own;
+        }
+    }
+    pinned
+}
+
+/// Whether any piece of `by` attacks `square`.
+pub fn is_attacked(game_state: &GameState, (row, col): (usize, usize), by: Player) -> bool {
+    is_square_attacked(game_state, square(row, col), by)
+}
+
+/// Every piece of `by` attacking `square`.
+pub fn attackers(game_state: &GameState, (row, col): (usize, usize), by: Player) -> SquareSet {
+    let attackers = attackers_to(game_state, square(row, col), game_state.occupied());
+    SquareSet(attackers & game_state.colors[by as usize])
+}
+
+/// Enemy pieces giving check to the side to move.
+pub fn checkers(game_state: &GameState) -> SquareSet {
+    let player = game_state.player_to_move;
+    match game_state.king_square(player) {
+        Some(king) => SquareSet(attackers_to(game_state, king, game_state.occupied()) &
+                                game_state.colors[player.opponent() as usize]),
+        None => SquareSet::default(),
+    }
+}
+
+/// Pieces of `player` pinned to their own king.
+pub fn pinned(game_state: &GameState, player: Player) -> SquareSet {
+    match game_state.king_square(player) {
+        Some(king) => SquareSet(pinned_pieces(game_state, king)),
+        None => SquareSet::default(),
+    }
+}
+
+/// Sliders of `by` that would attack `square` if the first piece in their
+/// way, of either side, were removed: the rook behind a rook, a queen
+/// behind a pinned piece and so on.
+pub fn xray_attackers(game_state: &GameState, (row, col): (usize, usize), by: Player) -> SquareSet {
+    let target = square(row, col);
+    let occupied = game_state.occupied();
+    let queens = game_state.pieces[PieceType::Queen as usize];
+
+    let rook_attacks_now = rook_attacks(target, occupied);
+    let rook_xray = rook_attacks(target, occupied & !rook_attacks_now) & !rook_attacks_now;
+    let bishop_attacks_now = bishop_attacks(target, occupied);
+    let bishop_xray = bishop_attacks(target, occupied & !bishop_attacks_now) & !bishop_attacks_now;
+
+    let xrays = rook_xray & (game_state.pieces[PieceType::Rook as usize] | queens) |
+                bishop_xray & (game_state.pieces[PieceType::Bishop as usize] | queens);
+    SquareSet(xrays & game_state.colors[by as usize])
+}
@@ -0,0 +1,133 @@
+//! Sets of squares packed into a `u64`. Square `line * 8 + column` is bit
+//! number `line * 8 + column`, so bit 0 is a8 and bit 63 is h1, matching the
+//! (line, column) coordinates used by the mailbox board.
+
+use std::fmt;
+
+use crate::game_state::square_name;
+
+pub(crate) type Bitboard = u64;
+
+pub(crate) const fn square(row: usize, col: usize) -> usize {
+    row * 8 + col
+}
+
+pub(crate) const fn row_col(square: usize) -> (usize, usize) {
+    (square / 8, square % 8)
+}
+
+pub(crate) const fn bit(square: usize) -> Bitboard {
+    1 << square
+}
+
+/// Iterates over the squares in a bitboard, lowest bit first.
+pub(crate) struct Squares(pub(crate) Bitboard);
+
+impl Iterator for Squares {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let square = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+}
+
+/// A set of squares, such as the attackers of a square. Iterating yields
+/// each square as (line, column).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SquareSet(pub(crate) Bitboard);
+
+impl SquareSet {
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        self.0 & bit(square(row, col)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Iterator for SquareSet {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        Squares(self.0).next().map(|square| {
+            self.0 &= self.0 - 1;
+            row_col(square)
+        })
+    }
+}
+
+impl fmt::Debug for SquareSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.map(|(row, col)| square_name(row, col))).finish()
+    }
+}
+
+// squares reachable from each square by one of the (line, column) offsets
+const fn step_attacks(offsets: &[(isize, isize)]) -> [Bitboard; 64] {
+    let mut table = [0; 64];
+    let mut sq = 0;
+    while sq < 64 {
+        let (row, col) = row_col(sq);
+        let mut i = 0;
+        while i < offsets.len() {
+            let o = row as isize + offsets[i].0;
+            let p = col as isize + offsets[i].1;
+            if o >= 0 && o <= 7 && p >= 0 && p <= 7 {
+                table[sq] |= bit(square(o as usize, p as usize));
+            }
+            i += 1;
+        }
+        sq += 1;
+    }
+    table
+}
+
+pub(crate) static KNIGHT_ATTACKS: [Bitboard; 64] =
+    step_attacks(&[(2, -1), (2, 1), (-2, -1), (-2, 1), (1, -2), (1, 2), (-1, -2), (-1, 2)]);
+
+pub(crate) static KING_ATTACKS: [Bitboard; 64] =
+    step_attacks(&[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)]);
+
+/// Squares attacked by a pawn, indexed by `[Player][square]`. White pawns
+/// move towards line 0.
+pub(crate) static PAWN_ATTACKS: [[Bitboard; 64]; 3] = [
+    [0; 64],
+    step_attacks(&[(-1, -1), (-1, 1)]),
+    step_attacks(&[(1, -1), (1, 1)]),
+];
+
+pub(crate) const ROOK_DIRECTIONS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+pub(crate) const BISHOP_DIRECTIONS: [(isize, isize); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+// walks each ray until it leaves the board or hits an occupied square,
+// which is included; only used to fill the lookup tables in `magic`
+pub(crate) fn ray_attacks(square_index: usize, occupied: Bitboard, directions: &[(isize, isize)]) -> Bitboard {
+    let mut attacks = 0;
+    let (row, col) = row_col(square_index);
+
+    for (dy, dx) in directions {
+        let mut o = row as isize + dy;
+        let mut p = col as isize + dx;
+        while (0..=7).contains(&o) && (0..=7).contains(&p) {
+            let target = bit(square(o as usize, p as usize));
+            attacks |= target;
+            if occupied & target != 0 {
+                break;
+            }
+            o += dy;
+            p += dx;
+        }
+    }
+
+    attacks
+}
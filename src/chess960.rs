@@ -0,0 +1,59 @@
+use crate::fen::fen_to_game_state;
+use crate::game_state::GameState;
+
+// the ways to place two knights on five empty squares, in Scharnagl order
+const KNIGHTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+/// The back rank of Chess960 starting position number `index`, from the
+/// a-file to the h-file, in FEN letters (e.g. "RNBQKBNR" for 518).
+fn back_rank(mut index: usize) -> [char; 8] {
+    let mut rank = [' '; 8];
+
+    // bishops on opposite colors: first the light-squared one on b, d, f
+    // or h, then the dark-squared one on a, c, e or g
+    rank[index % 4 * 2 + 1] = 'B';
+    index /= 4;
+    rank[index % 4 * 2] = 'B';
+    index /= 4;
+
+    let mut place_on_empty = |nth: usize, piece: char| {
+        let col = (0..8).filter(|col| rank[*col] == ' ').nth(nth).unwrap();
+        rank[col] = piece;
+    };
+
+    place_on_empty(index % 6, 'Q');
+    index /= 6;
+
+    // the second knight is counted after the first one has been placed
+    let (first, second) = KNIGHTS[index];
+    place_on_empty(first, 'N');
+    place_on_empty(second - 1, 'N');
+
+    // the king always ends up between the rooks
+    place_on_empty(0, 'R');
+    place_on_empty(0, 'K');
+    place_on_empty(0, 'R');
+
+    rank
+}
+
+/// The Chess960 starting position with Scharnagl number `index`, from 0 to
+/// 959; number 518 is the standard starting position. `None` for any other
+/// number.
+pub fn chess960_position(index: usize) -> Option<GameState> {
+    double_chess960_position(index, index)
+}
+
+/// A Double Fischer Random starting position: White's back rank is Chess960
+/// position `white` and Black's is position `black`, each from 0 to 959.
+/// `None` if either number is out of range.
+pub fn double_chess960_position(white: usize, black: usize) -> Option<GameState> {
+    if white >= 960 || black >= 960 {
+        return None;
+    }
+
+    let white_rank: String = back_rank(white).iter().collect();
+    let black_rank: String = back_rank(black).iter().collect();
+    let fen = format!("{}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1", black_rank.to_lowercase(), white_rank);
+    Some(fen_to_game_state(&fen).expect("Chess960 starting positions are valid"))
+}
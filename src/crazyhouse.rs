@@ -0,0 +1,49 @@
+//! Crazyhouse: captured pieces change sides and go into the capturer's
+//! pocket, from where they may later be dropped on any empty square instead
+//! of moving. Pockets appear in FEN in brackets after the placement, like
+//! `[Qn]`, and promoted pieces are marked with `~`, since they go back into
+//! a pocket as pawns. Drops themselves are made by `make_move`.
+
+use crate::fen::{parse_fen, FenError};
+use crate::game_state::GameState;
+use crate::result::GameResult;
+use crate::validation::{into_result, position_errors_except, PositionError};
+use crate::variant::{status_or_move_rule, Variant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Crazyhouse;
+
+impl Variant for Crazyhouse {
+    fn name(&self) -> &'static str {
+        "crazyhouse"
+    }
+
+    fn starting_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1"
+    }
+
+    // material moves between the sides through the pockets, so the limits
+    // on how many pieces of each kind one side can have do not apply
+    fn validate(&self, game_state: &GameState) -> Result<(), Vec<PositionError>> {
+        into_result(position_errors_except(game_state, |err| {
+            matches!(err, PositionError::PocketsNotAllowed |
+                          PositionError::TooManyPawns { .. } |
+                          PositionError::TooManyPieces { .. } |
+                          PositionError::TooManyPromotedPieces { .. })
+        }))
+    }
+
+    // a record without pockets starts with both of them empty
+    fn position_from_fen(&self, fen: &str) -> Result<GameState, FenError> {
+        let mut game_state = parse_fen(fen)?;
+        game_state.pockets.get_or_insert([[0; 7]; 3]);
+        self.validate(&game_state).map_err(FenError::InvalidPosition)?;
+
+        Ok(game_state)
+    }
+
+    // no draw by insufficient material: captured pieces always come back
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        status_or_move_rule(self, game_state)
+    }
+}
@@ -0,0 +1,397 @@
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+use regex::Regex;
+
+use crate::bitboard::{bit, square};
+use crate::game_state::{CastlingSide, GameState, PieceType, Player};
+use crate::validation::{validate_position, PositionError};
+use crate::zobrist::compute_zobrist_key;
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// The six space-separated sections of a FEN record, plus the optional
+/// seventh some variants use.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum FenField {
+    Placement,
+    SideToMove,
+    Castling,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+    /// Three-check's `+N+M`: checks given by White and by Black.
+    CheckCount,
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            FenField::Placement => "piece placement",
+            FenField::SideToMove => "side to move",
+            FenField::Castling => "castling rights",
+            FenField::EnPassant => "en passant target",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+            FenField::CheckCount => "check count",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Why a FEN string was rejected by `fen_to_game_state`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenError {
+    /// The record does not have six fields, or seven with a Three-check check count.
+    WrongFieldCount(usize),
+    /// The placement field does not describe exactly eight ranks.
+    WrongRankCount(usize),
+    /// A rank (numbered 8 down to 1) covers more or fewer than eight files.
+    BadRankLength { rank: u8, length: usize },
+    /// A symbol that is not allowed in the given field.
+    UnexpectedSymbol { field: FenField, symbol: String },
+    /// A letter other than K, Q, k, q or a file in the castling field, or
+    /// a right given twice.
+    InvalidCastling(String),
+    /// The en passant field is neither `-` nor a square like `e3`.
+    InvalidEnPassant(String),
+    /// The en passant square is not on the rank the side to move could capture on.
+    EnPassantWrongRank(String),
+    /// A move counter that is not a non-negative number.
+    InvalidCounter { field: FenField, value: String },
+    /// The check count is not `+N+M` with both numbers from 0 to 3.
+    InvalidCheckCount(String),
+    /// The record is well-formed but describes an impossible position.
+    InvalidPosition(Vec<PositionError>),
+}
+
+impl FenError {
+    /// The FEN field the error was found in, if it is specific to one.
+    pub fn field(&self) -> Option<FenField> {
+        match self {
+            FenError::WrongFieldCount(_) |
+            FenError::InvalidPosition(_) => None,
+            FenError::WrongRankCount(_) |
+            FenError::BadRankLength { .. } => Some(FenField::Placement),
+            FenError::UnexpectedSymbol { field, .. } => Some(*field),
+            FenError::InvalidCastling(_) => Some(FenField::Castling),
+            FenError::InvalidEnPassant(_) |
+            FenError::EnPassantWrongRank(_) => Some(FenField::EnPassant),
+            FenError::InvalidCounter { field, .. } => Some(*field),
+            FenError::InvalidCheckCount(_) => Some(FenField::CheckCount),
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(field) = self.field() {
+            write!(f, "{}: ", field)?;
+        }
+        match self {
+            FenError::WrongFieldCount(count) =>
+                write!(f, "expected 6 fields (7 in Three-check), found {}", count),
+            FenError::WrongRankCount(count) =>
+                write!(f, "expected 8 ranks, found {}", count),
+            FenError::BadRankLength { rank, length } =>
+                write!(f, "rank {} covers {} files instead of 8", rank, length),
+            FenError::UnexpectedSymbol { symbol, .. } =>
+                write!(f, "unexpected symbol '{}'", symbol),
+            FenError::InvalidCastling(symbol) =>
+                write!(f, "invalid or repeated letter '{}'", symbol),
+            FenError::InvalidEnPassant(value) =>
+                write!(f, "'{}' is not a square", value),
+            FenError::EnPassantWrongRank(value) =>
+                write!(f, "'{}' is not on the rank behind a double-pushed pawn", value),
+            FenError::InvalidCounter { value, .. } =>
+                write!(f, "'{}' is not a number", value),
+            FenError::InvalidCheckCount(value) =>
+                write!(f, "'{}' is not +N+M with N and M from 0 to 3", value),
+            FenError::InvalidPosition(errors) => {
+                let reasons: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+                write!(f, "impossible position: {}", reasons.join(", "))
+            },
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// Parses a FEN record, e.g. `STARTING_FEN`, rejecting positions that
+/// `validate_position` considers impossible.
+pub fn fen_to_game_state(raw_fen: &str) -> Result<GameState, FenError> {
+    let game_state = parse_fen(raw_fen)?;
+    validate_position(&game_state).map_err(FenError::InvalidPosition)?;
+
+    Ok(game_state)
+}
+
+/// Parses a FEN record without checking that the position makes sense.
+pub(crate) fn parse_fen(raw_fen: &str) -> Result<GameState, FenError> {
+    parse_fields(raw_fen, false)
+}
+
+/// Like `parse_fen`, but also accepts Three-check's `+N+M` as a seventh field.
+pub(crate) fn parse_three_check_fen(raw_fen: &str) -> Result<GameState, FenError> {
+    parse_fields(raw_fen, true)
+}
+
+fn parse_fields(raw_fen: &str, check_count: bool) -> Result<GameState, FenError> {
+    let fen = raw_fen.trim();
+
+    let mut game_state = GameState::empty();
+
+    let separator = Regex::new(r"([ ]+)").expect("Invalid regex");
+    let splits: Vec<_> = separator.split(fen).collect();
+    if splits.len() != 6 && !(check_count && splits.len() == 7) {
+        return Err(FenError::WrongFieldCount(splits.len()));
+    }
+
+    // section 0: pieces on the board, followed in Crazyhouse by the
+    // pockets in brackets, like [Qn], or as a ninth rank, like /Qn
+    let (placement, pockets) = match splits[0].split_once('[') {
+        Some((placement, pockets)) => {
+            let pockets = pockets.strip_suffix(']').ok_or_else(|| FenError::UnexpectedSymbol {
+                field: FenField::Placement, symbol: "[".to_string(),
+            })?;
+            (placement, Some(pockets))
+        },
+        None if splits[0].matches('/').count() == 8 => {
+            let (placement, pockets) = splits[0].rsplit_once('/').unwrap();
+            (placement, Some(pockets))
+        },
+        None => (splits[0], None),
+    };
+    let ranks: Vec<_> = placement.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(FenError::WrongRankCount(ranks.len()));
+    }
+    for (cur_row, rank) in ranks.iter().enumerate() {
+        let mut cur_col: usize = 0;
+        for c in UnicodeSegmentation::graphemes(*rank, true) {
+            let (piece, owner) = match c {
+                "1"|"2"|"3"|"4"|"5"|"6"|"7"|"8" => {
+                    cur_col += c.parse::<usize>().unwrap();
+                    continue;
+                },
+                "k" => (PieceType::King, Player::Black),
+                "K" => (PieceType::King, Player::White),
+                "b" => (PieceType::Bishop, Player::Black),
+                "B" => (PieceType::Bishop, Player::White),
+                "r" => (PieceType::Rook, Player::Black),
+                "R" => (PieceType::Rook, Player::White),
+                "n" => (PieceType::Knight, Player::Black),
+                "N" => (PieceType::Knight, Player::White),
+                "q" => (PieceType::Queen, Player::Black),
+                "Q" => (PieceType::Queen, Player::White),
+                "p" => (PieceType::Pawn, Player::Black),
+                "P" => (PieceType::Pawn, Player::White),
+                // marks the piece before it as a promoted pawn
+                "~" if (1..=8).contains(&cur_col) && game_state.owner_at(cur_row, cur_col - 1) != Player::None => {
+                    game_state.promoted |= bit(square(cur_row, cur_col - 1));
+                    continue;
+                },
+                _ => return Err(FenError::UnexpectedSymbol { field: FenField::Placement, symbol: c.to_string() }),
+            };
+            if cur_col < 8 {
+                game_state.set_piece_at(cur_row, cur_col, piece, owner);
+            }
+            cur_col += 1;
+        }
+        if cur_col != 8 {
+            return Err(FenError::BadRankLength { rank: 8 - cur_row as u8, length: cur_col });
+        }
+    }
+
+    if let Some(pockets) = pockets {
+        let mut counts = [[0u8; 7]; 3];
+        for c in pockets.chars() {
+            let piece = PieceType::from_letter(c).filter(|piece| *piece != PieceType::King);
+            let piece = piece.ok_or_else(|| FenError::UnexpectedSymbol {
+                field: FenField::Placement, symbol: c.to_string(),
+            })?;
+            let owner = if c.is_ascii_uppercase() { Player::White } else { Player::Black };
+            let count = &mut counts[owner as usize][piece as usize];
+            *count = count.saturating_add(1);
+        }
+        game_state.pockets = Some(counts);
+    }
+
+    // section 1: player to move
+    game_state.player_to_move = match splits[1] {
+        "w" => Player::White,
+        "b" => Player::Black,
+        _ => return Err(FenError::UnexpectedSymbol { field: FenField::SideToMove, symbol: splits[1].to_string() }),
+    };
+
+    // section 2: castling rights
+    // K and Q pick the outermost rook on that side of the king (standard
+    // FEN and X-FEN); a file letter names the rook directly (Shredder-FEN
+    // and X-FEN), for Chess960 positions with several rooks on one side
+    if splits[2] != "-" {
+        for c in UnicodeSegmentation::graphemes(splits[2], true) {
+            let invalid = || FenError::InvalidCastling(c.to_string());
+            let letter = c.chars().next().filter(|_| c.len() == 1).ok_or_else(invalid)?;
+            let player = if letter.is_ascii_uppercase() { Player::White } else { Player::Black };
+            let row = player.home_row();
+            let own_rook = |col: &usize| game_state.piece_at(row, *col) == (PieceType::Rook, player);
+            let king_col = (0..8).find(|col| game_state.piece_at(row, *col) == (PieceType::King, player)).unwrap_or(4);
+
+            let (side, file) = match letter.to_ascii_lowercase() {
+                'k' => (CastlingSide::Kingside, (king_col + 1..8).rev().find(own_rook).unwrap_or(7)),
+                'q' => (CastlingSide::Queenside, (0..king_col).find(own_rook).unwrap_or(0)),
+                file @ 'a'..='h' => {
+                    let file = (file as u8 - b'a') as usize;
+                    (if file > king_col { CastlingSide::Kingside } else { CastlingSide::Queenside }, file)
+                },
+                _ => return Err(invalid()),
+            };
+
+            let rights = &mut game_state.castling_rights;
+            if rights.allowed(player, side) {
+                return Err(invalid());
+            }
+            rights.set_rook_file(player, side, Some(file));
+        }
+    }
+
+    // section 3: en passant target
+    // stored as the square of the pawn that just made the double push
+    if splits[3] != "-" {
+        let square = splits[3].as_bytes();
+        if square.len() != 2 || !(b'a'..=b'h').contains(&square[0]) || !(b'1'..=b'8').contains(&square[1]) {
+            return Err(FenError::InvalidEnPassant(splits[3].to_string()));
+        }
+        let file = square[0] - b'a';
+        let pawn_row = match (game_state.player_to_move, square[1]) {
+            (Player::White, b'6') => 3,
+            (Player::Black, b'3') => 4,
+            _ => return Err(FenError::EnPassantWrongRank(splits[3].to_string())),
+        };
+        game_state.en_passant_target = Some((pawn_row, file));
+    }
+
+    // section 4: halfmove counter
+    game_state.halfmove_counter = splits[4].parse::<u16>()
+        .map_err(|_| FenError::InvalidCounter { field: FenField::HalfmoveClock, value: splits[4].to_string() })?;
+
+    // section 5: fullmove counter
+    game_state.fullmove_counter = splits[5].parse::<u16>()
+        .map_err(|_| FenError::InvalidCounter { field: FenField::FullmoveNumber, value: splits[5].to_string() })?;
+
+    // section 6 (Three-check only): checks given, as +White+Black
+    if let Some(checks) = splits.get(6) {
+        let invalid = || FenError::InvalidCheckCount(checks.to_string());
+        let counts: Vec<_> = checks.strip_prefix('+').ok_or_else(invalid)?.split('+').collect();
+        if counts.len() != 2 {
+            return Err(invalid());
+        }
+        let parse_count = |count: &str| count.parse::<u8>().ok().filter(|count| *count <= 3).ok_or_else(invalid);
+        let mut given = [0; 3];
+        given[Player::White as usize] = parse_count(counts[0])?;
+        given[Player::Black as usize] = parse_count(counts[1])?;
+        game_state.checks_given = Some(given);
+    }
+
+    game_state.zobrist_key = compute_zobrist_key(&game_state);
+
+    Ok(game_state)
+}
+
+/// Serializes a position back into a FEN record.
+pub fn game_state_to_fen(game_state: &GameState) -> String {
+    let mut fen = String::new();
+
+    // section 0: pieces on the board
+    for row in 0..8 {
+        let mut empty = 0;
+        for col in 0..8 {
+            if game_state.piece_at(row, col).0 == PieceType::None {
+                empty += 1;
+                continue;
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+                empty = 0;
+            }
+            fen.push_str(&game_state.board[row][col].to_string());
+            if game_state.is_promoted(row, col) {
+                fen.push('~');
+            }
+        }
+        if empty > 0 {
+            fen.push_str(&empty.to_string());
+        }
+        if row < 7 {
+            fen.push('/');
+        }
+    }
+    if let Some(pockets) = game_state.pockets {
+        fen.push('[');
+        for player in [Player::White, Player::Black] {
+            for piece in [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight, PieceType::Pawn] {
+                let letter = if player == Player::White { piece.letter() } else { piece.letter().to_ascii_lowercase() };
+                for _ in 0..pockets[player as usize][piece as usize] {
+                    fen.push(letter);
+                }
+            }
+        }
+        fen.push(']');
+    }
+
+    // section 1: player to move
+    fen.push_str(match game_state.player_to_move {
+        Player::Black => " b ",
+        _ => " w ",
+    });
+
+    // section 2: castling rights, as X-FEN: K and Q when the castling rook
+    // is the outermost one on its side of the king, its file otherwise
+    let rights = game_state.castling_rights;
+    let mut castling = String::new();
+    for player in [Player::White, Player::Black] {
+        for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+            let file = match rights.rook_file(player, side) {
+                Some(file) => file,
+                None => continue,
+            };
+            let row = player.home_row();
+            let beyond = match side {
+                CastlingSide::Kingside => file + 1..8,
+                CastlingSide::Queenside => 0..file,
+            };
+            let outermost = beyond.into_iter().all(|col| game_state.piece_at(row, col) != (PieceType::Rook, player));
+            let symbol = match (outermost, side) {
+                (true, CastlingSide::Kingside) => 'k',
+                (true, CastlingSide::Queenside) => 'q',
+                (false, _) => (b'a' + file as u8) as char,
+            };
+            castling.push(if player == Player::White { symbol.to_ascii_uppercase() } else { symbol });
+        }
+    }
+    if castling.is_empty() {
+        fen.push('-');
+    } else {
+        fen.push_str(&castling);
+    }
+
+    // section 3: en passant target, the square behind the double-pushed pawn
+    fen.push(' ');
+    match game_state.en_passant_target {
+        Some((pawn_row, file)) => {
+            let rank = if pawn_row == 3 { '6' } else { '3' };
+            fen.push((b'a' + file) as char);
+            fen.push(rank);
+        },
+        None => fen.push('-'),
+    }
+
+    // sections 4 and 5: move counters
+    fen.push_str(&format!(" {} {}", game_state.halfmove_counter, game_state.fullmove_counter));
+
+    // section 6: checks given, only in variants that count them
+    if let Some(checks) = game_state.checks_given {
+        fen.push_str(&format!(" +{}+{}", checks[Player::White as usize], checks[Player::Black as usize]));
+    }
+
+    fen
+}
@@ -0,0 +1,158 @@
+use crate::bitboard::Bitboard;
+use crate::fen::{fen_to_game_state, FenError};
+use crate::game_state::{CastlingRights, GameState, Player, Square};
+use crate::moves::{Move, MoveKind, MoveList};
+use crate::result::{is_fifty_move_draw, DrawReason, GameResult};
+use crate::variant::{Standard, Variant};
+
+/// What makes two positions "the same" for the repetition rules: pieces,
+/// side to move, castling rights, the en passant square only when an en
+/// passant capture is actually possible under the variant's rules, and any
+/// variant-specific state.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct PositionKey {
+    board: [[Square; 8]; 8],
+    player_to_move: Player,
+    castling_rights: CastlingRights,
+    en_passant_target: Option<(u8, u8)>,
+    checks_given: Option<[u8; 3]>,
+    pockets: Option<[[u8; 7]; 3]>,
+    promoted: Bitboard,
+}
+
+impl PositionKey {
+    fn new(variant: &dyn Variant, game_state: &GameState) -> PositionKey {
+        let en_passant = game_state.en_passant_target.is_some() &&
+            variant.legal_moves(game_state).iter().any(|mv| mv.kind() == MoveKind::EnPassant);
+
+        PositionKey {
+            board: game_state.board,
+            player_to_move: game_state.player_to_move,
+            castling_rights: game_state.castling_rights,
+            en_passant_target: if en_passant {
+                game_state.en_passant_target
+            } else {
+                None
+            },
+            checks_given: game_state.checks_given,
+            pockets: game_state.pockets,
+            promoted: game_state.promoted,
+        }
+    }
+}
+
+/// A game played from a starting position, keeping every position and move
+/// so that rules depending on history (like repetition) can be applied.
+/// Moves are played and results decided by the game's `Variant`.
+#[derive(Clone)]
+pub struct Game {
+    variant: &'static dyn Variant,
+    // positions[0] is the starting position, positions[i] the one after moves[i-1]
+    positions: Vec<GameState>,
+    keys: Vec<PositionKey>,
+    moves: Vec<Move>,
+}
+
+impl Game {
+    pub fn new(start: GameState) -> Game {
+        Game::with_variant(start, &Standard)
+    }
+
+    pub fn with_variant(start: GameState, variant: &'static dyn Variant) -> Game {
+        Game {
+            variant,
+            positions: vec![start],
+            keys: vec![PositionKey::new(variant, &start)],
+            moves: vec![],
+        }
+    }
+
+    pub fn from_fen(fen: &str) -> Result<Game, FenError> {
+        Ok(Game::new(fen_to_game_state(fen)?))
+    }
+
+    pub fn variant(&self) -> &'static dyn Variant {
+        self.variant
+    }
+
+    /// The position after the last move played.
+    pub fn current(&self) -> &GameState {
+        self.positions.last().unwrap()
+    }
+
+    /// Every position so far, starting with the initial one.
+    pub fn positions(&self) -> &[GameState] {
+        &self.positions
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Legal moves in the current position.
+    pub fn legal_moves(&self) -> MoveList {
+        self.variant.legal_moves(self.current())
+    }
+
+    /// Plays `mv`, which must be legal in the current position.
+    pub fn play(&mut self, mv: Move) {
+        let mut game_state = *self.current();
+        self.variant.play(&mut game_state, mv);
+
+        self.keys.push(PositionKey::new(self.variant, &game_state));
+        self.positions.push(game_state);
+        self.moves.push(mv);
+    }
+
+    /// Takes back the last move, returning it.
+    pub fn undo(&mut self) -> Option<Move> {
+        let mv = self.moves.pop()?;
+        self.positions.pop();
+        self.keys.pop();
+        Some(mv)
+    }
+
+    /// How many times the current position has occurred, including now.
+    pub fn repetition_count(&self) -> usize {
+        let current = self.keys.last().unwrap();
+        self.keys.iter().filter(|key| *key == current).count()
+    }
+
+    /// The current position occurred three times; either side may claim a draw.
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetition_count() >= 3
+    }
+
+    /// The current position occurred five times; the game is drawn automatically.
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetition_count() >= 5
+    }
+
+    /// A draw the side to move may claim but that does not end the game by
+    /// itself. Nothing can be claimed once the game is over.
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.result().is_some() {
+            None
+        } else if self.is_threefold_repetition() {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if is_fifty_move_draw(self.current()) {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    /// The result if the game has ended, by checkmate, stalemate or an
+    /// automatic draw. Claimable draws are reported by `claimable_draw`.
+    pub fn result(&self) -> Option<GameResult> {
+        if let Some(result) = self.variant.game_result(self.current()) {
+            return Some(result);
+        }
+
+        if self.is_fivefold_repetition() {
+            return Some(GameResult::Draw { reason: DrawReason::FivefoldRepetition });
+        }
+
+        None
+    }
+}
@@ -0,0 +1,345 @@
+use std::fmt;
+use bitfield::bitfield;
+use num_enum::TryFromPrimitive;
+use num_enum::IntoPrimitive;
+
+use crate::bitboard::{bit, square, Bitboard, Squares};
+use crate::zobrist::{en_passant_key, piece_key, pocket_key, promoted_key, side_to_move_key};
+
+#[derive(Debug, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive, Copy, Clone)]
+#[repr(u8)]
+pub enum PieceType {
+    None,
+    Pawn,
+    Rook,
+    Knight,
+    Bishop,
+    Queen,
+    King,
+}
+
+impl PieceType {
+    /// Pieces a pawn can promote to.
+    pub(crate) fn iterator() -> impl Iterator<Item = PieceType> {
+        [PieceType::Rook, PieceType::Knight, PieceType::Bishop, PieceType::Queen].iter().copied()
+    }
+
+    /// Uppercase FEN letter of the piece; '.' for `PieceType::None`.
+    pub(crate) fn letter(&self) -> char {
+        match self {
+            PieceType::Rook => 'R',
+            PieceType::Knight => 'N',
+            PieceType::Bishop => 'B',
+            PieceType::Queen => 'Q',
+            PieceType::King => 'K',
+            PieceType::Pawn => 'P',
+            PieceType::None => '.',
+        }
+    }
+
+    /// The piece a FEN letter of either case stands for.
+    pub(crate) fn from_letter(letter: char) -> Option<PieceType> {
+        match letter.to_ascii_uppercase() {
+            'R' => Some(PieceType::Rook),
+            'N' => Some(PieceType::Knight),
+            'B' => Some(PieceType::Bishop),
+            'Q' => Some(PieceType::Queen),
+            'K' => Some(PieceType::King),
+            'P' => Some(PieceType::Pawn),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive, Copy, Clone)]
+#[repr(u8)]
+pub enum Player {
+    None,
+    White,
+    Black
+}
+
+impl Player {
+    /// The other side; `Player::None` stays `Player::None`.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::White => Player::Black,
+            Player::Black => Player::White,
+            Player::None => Player::None,
+        }
+    }
+}
+
+bitfield!{
+    #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Square(u8);
+    impl Debug;
+    pub get_piece, set_piece: 3, 0;
+    pub get_owner, set_owner: 5, 4;
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut symbol = PieceType::try_from(self.get_piece()).unwrap().letter();
+        if self.get_owner() == Player::Black as u8 {
+            symbol = symbol.to_ascii_lowercase();
+        }
+        write!(f, "{}", symbol)?;
+        Ok(())
+    }
+}
+
+/// Algebraic name of the square at (line, column), e.g. (7,4) is "e1".
+pub(crate) fn square_name(row: usize, col: usize) -> String {
+    format!("{}{}", (b'a' + col as u8) as char, 8 - row)
+}
+
+/// Side of the board a castling right refers to.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CastlingSide {
+    Kingside,
+    Queenside,
+}
+
+bitfield!{
+    /// The castling rights of both sides, each stored as the file of the rook
+    /// it castles with plus one, or 0 once the right is gone. The files are
+    /// the a- and h-file in standard chess, but may be any file on either
+    /// side of the king, and differ between White and Black, in Chess960 and
+    /// Double Fischer Random.
+    #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct CastlingRights(u16);
+    impl Debug;
+    pub u8, get_black_queenside_rook, set_black_queenside_rook: 3, 0;
+    pub u8, get_black_kingside_rook, set_black_kingside_rook: 7, 4;
+    pub u8, get_white_queenside_rook, set_white_queenside_rook: 11, 8;
+    pub u8, get_white_kingside_rook, set_white_kingside_rook: 15, 12;
+}
+
+impl CastlingRights {
+    /// No castling allowed for either side.
+    pub(crate) fn none() -> CastlingRights {
+        CastlingRights(0)
+    }
+
+    /// File of the rook `player` may still castle with on `side`, if any.
+    pub(crate) fn rook_file(&self, player: Player, side: CastlingSide) -> Option<usize> {
+        let stored = match (player, side) {
+            (Player::White, CastlingSide::Kingside) => self.get_white_kingside_rook(),
+            (Player::White, CastlingSide::Queenside) => self.get_white_queenside_rook(),
+            (Player::Black, CastlingSide::Kingside) => self.get_black_kingside_rook(),
+            (Player::Black, CastlingSide::Queenside) => self.get_black_queenside_rook(),
+            (Player::None, _) => 0,
+        };
+        (stored as usize).checked_sub(1)
+    }
+
+    pub(crate) fn allowed(&self, player: Player, side: CastlingSide) -> bool {
+        self.rook_file(player, side).is_some()
+    }
+
+    /// Grants the right to castle with the rook on `file`, or with `None`
+    /// takes it away.
+    pub(crate) fn set_rook_file(&mut self, player: Player, side: CastlingSide, file: Option<usize>) {
+        let stored = file.map_or(0, |file| file as u8 + 1);
+        match (player, side) {
+            (Player::White, CastlingSide::Kingside) => self.set_white_kingside_rook(stored),
+            (Player::White, CastlingSide::Queenside) => self.set_white_queenside_rook(stored),
+            (Player::Black, CastlingSide::Kingside) => self.set_black_kingside_rook(stored),
+            (Player::Black, CastlingSide::Queenside) => self.set_black_queenside_rook(stored),
+            (Player::None, _) => panic!("Invalid player for castling"),
+        }
+    }
+}
+
+impl Player {
+    /// Line of this side's first rank, where the king and rooks castle.
+    pub(crate) fn home_row(&self) -> usize {
+        match self {
+            Player::White => 7,
+            _ => 0,
+        }
+    }
+}
+
+/// A full position: piece placement plus the side to move, castling rights,
+/// en passant target and move counters. Build one with `fen_to_game_state`,
+/// or edit one square by square starting from `GameState::empty`.
+#[derive(Debug, Copy, Clone)]
+pub struct GameState {
+    // (line, column) where (0,0) is black's rook and white king is at (7,4)
+    pub(crate) board: [[Square; 8]; 8],
+    // the same placement as bitboards, indexed by PieceType and by Player;
+    // index 0 (None) is always empty
+    pub(crate) pieces: [Bitboard; 7],
+    pub(crate) colors: [Bitboard; 3],
+    pub(crate) player_to_move: Player,
+    pub(crate) castling_rights: CastlingRights,
+    // square of the pawn that just made a double push, not the FEN target square
+    pub(crate) en_passant_target: Option<(u8, u8)>,
+    pub(crate) halfmove_counter: u16,
+    pub(crate) fullmove_counter: u16,
+    // checks each side has given, indexed by Player, for Three-check; None
+    // in variants that do not count them
+    pub(crate) checks_given: Option<[u8; 3]>,
+    // pieces in hand for Crazyhouse, indexed by Player and PieceType; None
+    // in variants without drops
+    pub(crate) pockets: Option<[[u8; 7]; 3]>,
+    // pieces that promoted from pawns, which go back into a pocket as pawns
+    // when captured; only tracked when there are pockets
+    pub(crate) promoted: Bitboard,
+    pub(crate) zobrist_key: u64,
+}
+
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.board {
+            for square in row {
+                write!(f, "{} ", square)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// An empty board with White to move, no castling rights and no en
+    /// passant target. Place pieces with `set_piece_at`, then check the
+    /// result with `validate_position` before generating moves for it.
+    pub fn empty() -> GameState {
+        GameState {
+            board: [[Square(0); 8]; 8],
+            pieces: [0; 7],
+            colors: [0; 3],
+            player_to_move: Player::White,
+            castling_rights: CastlingRights::none(),
+            en_passant_target: None,
+            halfmove_counter: 0,
+            fullmove_counter: 1,
+            checks_given: None,
+            pockets: None,
+            promoted: 0,
+            zobrist_key: 0,
+        }
+    }
+
+    /// Puts `piece` of `owner` on (line, column), replacing whatever stood
+    /// there; `(PieceType::None, Player::None)` empties the square. Castling
+    /// rights and the en passant target are left alone, so an edited
+    /// position may no longer pass `validate_position`.
+    pub fn set_piece_at(&mut self, row: usize, col: usize, piece: PieceType, owner: Player) {
+        assert!((owner == Player::None && piece == PieceType::None) ||
+                (owner != Player::None && piece != PieceType::None));
+        let (old_piece, old_owner) = self.piece_at(row, col);
+        self.zobrist_key ^= piece_key(old_piece, old_owner, row, col) ^ piece_key(piece, owner, row, col);
+        let square_bit = bit(square(row, col));
+        self.pieces[old_piece as usize] &= !square_bit;
+        self.colors[old_owner as usize] &= !square_bit;
+        if piece != PieceType::None {
+            self.pieces[piece as usize] |= square_bit;
+            self.colors[owner as usize] |= square_bit;
+        }
+        self.board[row][col].set_piece(piece.into());
+        self.board[row][col].set_owner(owner.into());
+    }
+
+    /// Gives the move to `player`, dropping the en passant target, which
+    /// only ever belongs to the side that was to move.
+    pub fn set_player_to_move(&mut self, player: Player) {
+        assert!(player != Player::None);
+        if player != self.player_to_move {
+            self.zobrist_key ^= side_to_move_key() ^ en_passant_key(self.en_passant_target);
+            self.player_to_move = player;
+            self.en_passant_target = None;
+        }
+    }
+
+    /// Puts one `piece` into `player`'s pocket.
+    pub(crate) fn add_to_pocket(&mut self, player: Player, piece: PieceType) {
+        if let Some(pockets) = &mut self.pockets {
+            let count = &mut pockets[player as usize][piece as usize];
+            self.zobrist_key ^= pocket_key(player, piece, *count) ^ pocket_key(player, piece, *count + 1);
+            *count += 1;
+        }
+    }
+
+    /// Takes one `piece` out of `player`'s pocket, which must hold one.
+    pub(crate) fn remove_from_pocket(&mut self, player: Player, piece: PieceType) {
+        if let Some(pockets) = &mut self.pockets {
+            let count = &mut pockets[player as usize][piece as usize];
+            self.zobrist_key ^= pocket_key(player, piece, *count) ^ pocket_key(player, piece, *count - 1);
+            *count -= 1;
+        }
+    }
+
+    pub(crate) fn set_promoted(&mut self, promoted: Bitboard) {
+        for changed in Squares(self.promoted ^ promoted) {
+            self.zobrist_key ^= promoted_key(changed);
+        }
+        self.promoted = promoted;
+    }
+
+    /// Piece and owner at (line, column), where (0,0) is a8 and (7,7) is h1.
+    /// Empty squares are reported as `(PieceType::None, Player::None)`.
+    pub fn piece_at(&self, row: usize, col: usize) -> (PieceType, Player) {
+        let square = self.board[row][col];
+        (PieceType::try_from(square.get_piece()).unwrap(),
+         Player::try_from(square.get_owner()).unwrap())
+    }
+
+    pub(crate) fn owner_at(&self, row: usize, col: usize) -> Player {
+        Player::try_from(self.board[row][col].get_owner()).unwrap()
+    }
+
+    pub(crate) fn pieces_of(&self, piece: PieceType, owner: Player) -> Bitboard {
+        self.pieces[piece as usize] & self.colors[owner as usize]
+    }
+
+    pub(crate) fn occupied(&self) -> Bitboard {
+        self.colors[Player::White as usize] | self.colors[Player::Black as usize]
+    }
+
+    /// Square index of `player`'s king, if it has one.
+    pub(crate) fn king_square(&self, player: Player) -> Option<usize> {
+        let kings = self.pieces_of(PieceType::King, player);
+        if kings == 0 {
+            None
+        } else {
+            Some(kings.trailing_zeros() as usize)
+        }
+    }
+
+    pub fn player_to_move(&self) -> Player {
+        self.player_to_move
+    }
+
+    pub fn halfmove_counter(&self) -> u16 {
+        self.halfmove_counter
+    }
+
+    pub fn fullmove_counter(&self) -> u16 {
+        self.fullmove_counter
+    }
+
+    /// Checks `player` has given so far, in variants that count them.
+    pub fn checks_given(&self, player: Player) -> Option<u8> {
+        self.checks_given.map(|checks| checks[player as usize])
+    }
+
+    /// How many of `piece` `player` holds in hand, in variants with drops.
+    pub fn pocket(&self, player: Player, piece: PieceType) -> Option<u8> {
+        self.pockets.map(|pockets| pockets[player as usize][piece as usize])
+    }
+
+    /// Whether the piece at (line, column) is a promoted pawn, in variants
+    /// where that matters.
+    pub fn is_promoted(&self, row: usize, col: usize) -> bool {
+        self.promoted & bit(square(row, col)) != 0
+    }
+
+    /// 64-bit Zobrist key of the position, kept up to date by `make_move`.
+    pub fn zobrist_key(&self) -> u64 {
+        self.zobrist_key
+    }
+}
@@ -1,15 +1,15 @@
-#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
-
-use crate::perft_divide;
-use crate::make_move;
-use crate::generate_legal_moves;
-use crate::perft;
+use xadreis::perft_divide;
+use xadreis::generate_legal_moves;
 use egui_extras::Size;
 use egui_extras::TableBuilder;
-use crate::fen_to_game_state;
-use crate::PieceType;
-use crate::GameState;
-use crate::Player;
+use xadreis::fen_to_game_state;
+use xadreis::FenError;
+use xadreis::game_state_to_fen;
+use xadreis::game_result;
+use xadreis::GameResult;
+use xadreis::PieceType;
+use xadreis::GameState;
+use xadreis::Player;
 use egui_extras::RetainedImage;
 use crate::gui::emath::vec2;
 use crate::gui::egui::Window;
@@ -22,8 +22,10 @@ pub fn gui(game_state: Box<GameState>, fen: String) {
     // Log to stdout (if you run with `RUST_LOG=debug`).
     tracing_subscriber::fmt::init();
 
-    let mut options = eframe::NativeOptions::default();
-    options.initial_window_size = Some(emath::Vec2{x:1200.0,y:800.0});
+    let options = eframe::NativeOptions {
+        initial_window_size: Some(emath::Vec2{x:1200.0,y:800.0}),
+        ..eframe::NativeOptions::default()
+    };
     let app = XadreisGUI::from_game_state(game_state, fen);
     eframe::run_native(
         "Xadreis",
@@ -49,17 +51,27 @@ struct XadreisGUI {
 
     game_state: Option<Box<GameState>>,
     fen: String,
+    fen_error: Option<FenError>,
+    result: Option<GameResult>,
 
     perft: Option<[isize; 8]>,
 }
 
 impl XadreisGUI {
     fn from_game_state(game_state: Box<GameState>, fen: String) -> Self {
-        let mut s = Self::default();
-        s.game_state = Some(game_state);
-        s.fen = fen;
+        Self {
+            result: game_result(&game_state),
+            game_state: Some(game_state),
+            fen,
+            ..Self::default()
+        }
+    }
 
-        return s;
+    fn set_game_state(&mut self, game_state: GameState) {
+        self.fen = game_state_to_fen(&game_state);
+        self.result = game_result(&game_state);
+        self.game_state = Some(Box::new(game_state));
+        self.fen_error = None;
     }
 }
 
@@ -82,6 +94,8 @@ impl Default for XadreisGUI {
         
             game_state: None,
             fen: "".to_string(),
+            fen_error: None,
+            result: None,
 
             perft: None,
         }
@@ -99,7 +113,20 @@ impl eframe::App for XadreisGUI {
                 let response = ui.add(egui::TextEdit::singleline(&mut self.fen).desired_width(f32::INFINITY).hint_text("Paste FEN here..."));
                 if response.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
                     self.fen = self.fen.trim().to_string();
-                    self.game_state = Some(Box::new(fen_to_game_state(self.fen.to_string())));
+                    match fen_to_game_state(&self.fen) {
+                        Ok(game_state) => { self.set_game_state(game_state); },
+                        Err(err) => { self.fen_error = Some(err); },
+                    }
+                }
+                if let Some(err) = &self.fen_error {
+                    ui.colored_label(Color32::RED, format!("Invalid FEN: {}", err));
+                }
+                if let Some(game_state) = &self.game_state {
+                    let text = match self.result {
+                        Some(result) => format!("{:?} ({})", result, result),
+                        None => format!("{:?} to move", game_state.player_to_move()),
+                    };
+                    ui.label(text);
                 }
                 Frame::canvas(ui.style()).show(ui, |ui| {
                     ui.ctx().request_repaint();
@@ -140,7 +167,7 @@ impl eframe::App for XadreisGUI {
 
                     ui.painter().extend(shapes);
 
-                    if self.game_state.is_some() {
+                    if let Some(game_state) = &self.game_state {
                         for i in 0..8 {
                             for j in 0..8 {
                                 let tile_size = board_rect.width()/8.0;
@@ -149,9 +176,10 @@ impl eframe::App for XadreisGUI {
 
                                 let mut offset = egui::vec2(tile_size * 0.2, tile_size * 0.2);
 
-                                let img = match Player::try_from(self.game_state.as_ref().unwrap().board[i][j].get_owner()).unwrap() {
+                                let (piece, owner) = game_state.piece_at(i, j);
+                                let img = match owner {
                                     Player::Black => {
-                                        match PieceType::try_from(self.game_state.as_ref().unwrap().board[i][j].get_piece()).unwrap() {
+                                        match piece {
                                             PieceType::Rook => &self.brook,
                                             PieceType::Knight => &self.bknight,
                                             PieceType::Bishop => &self.bbishop,
@@ -162,7 +190,7 @@ impl eframe::App for XadreisGUI {
                                         }
                                     },
                                     Player::White => {
-                                        match PieceType::try_from(self.game_state.as_ref().unwrap().board[i][j].get_piece()).unwrap() {
+                                        match piece {
                                             PieceType::Rook => &self.wrook,
                                             PieceType::Knight => &self.wknight,
                                             PieceType::Bishop => &self.wbishop,
@@ -194,22 +222,15 @@ impl eframe::App for XadreisGUI {
         Window::new("Perft")
             .open(&mut open)
             .show(ctx, |top_ui| {
-                if top_ui.button("Run perft() for current board position").clicked() {
-                    // TODO:
-                    //  - understand this & + as_ref() stuff
-                    //  - stop unwrap()'ing here, we could very easily crash
-                    let moves = generate_legal_moves(&self.game_state.as_ref().unwrap());
-                    println!("Perft(1) moves: {:?}", moves);
-                    // for mv in &moves {
-                    //     let mut tmp_game = self.game_state.as_ref().unwrap().clone();
-                    //     make_move(&mut tmp_game, *mv);
-                    //     println!("{}", tmp_game);
-                    //     println!("Perft moves: {:?}", generate_legal_moves(&tmp_game));
-                    // }
-
-                    let mut perft_results = [-1isize; 8];
-                    Some(perft_divide(&mut perft_results, &self.game_state.as_ref().unwrap(), 3));
-                    self.perft = Some(perft_results);
+                if let Some(game_state) = &self.game_state {
+                    if top_ui.button("Run perft() for current board position").clicked() {
+                        let moves = generate_legal_moves(game_state);
+                        println!("Perft(1) moves: {:?}", moves);
+
+                        let mut perft_results = [-1isize; 8];
+                        perft_divide(&mut perft_results, game_state, 3);
+                        self.perft = Some(perft_results);
+                    }
                 }
 
                 let table = TableBuilder::new(top_ui)
@@ -230,8 +251,8 @@ impl eframe::App for XadreisGUI {
                         body.row(20.0, |mut row| {
                             row.col(|rui| { rui.label("Nodes"); });
                             for i in 1..8 {
-                                if self.perft.is_some() {
-                                    let count = self.perft.unwrap()[i];
+                                if let Some(perft) = self.perft {
+                                    let count = perft[i];
 
                                     if count != -1 {
                                         row.col(|rui| { rui.label(count.to_string()); });
@@ -247,4 +268,3 @@ impl eframe::App for XadreisGUI {
                 });
     }
 }
-
@@ -0,0 +1,70 @@
+//! Horde: White has no king, only a horde of 36 pawns, and wins by
+//! checkmating Black, who wins by capturing the whole horde. Pawns on the
+//! first rank may double-step like those on the second.
+
+use crate::attacks::is_player_in_check;
+use crate::game_state::{GameState, Player};
+use crate::result::{GameResult, WinReason};
+use crate::validation::{into_result, position_errors_except, validate_position, PositionError};
+use crate::variant::{status_or_move_rule, Variant};
+
+// up to 36 pawns, or what they promote to
+const MAX_HORDE: usize = 36;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Horde;
+
+impl Variant for Horde {
+    fn name(&self) -> &'static str {
+        "horde"
+    }
+
+    fn starting_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1"
+    }
+
+    // the horde is whichever side has no king, and may have pawns on its
+    // first rank and up to 36 pieces
+    fn validate(&self, game_state: &GameState) -> Result<(), Vec<PositionError>> {
+        let horde = match [Player::White, Player::Black].into_iter()
+            .find(|player| game_state.king_square(*player).is_none()) {
+            Some(horde) if game_state.king_square(horde.opponent()).is_some() => horde,
+            _ => return validate_position(game_state),
+        };
+
+        let mut errors = position_errors_except(game_state, |err| match err {
+            PositionError::WrongKingCount { player, .. } |
+            PositionError::TooManyPawns { player, .. } |
+            PositionError::TooManyPieces { player, .. } |
+            PositionError::TooManyPromotedPieces { player } => *player == horde,
+            PositionError::PawnOnBackRank { square: (row, col) } =>
+                *row == horde.home_row() && game_state.owner_at(*row, *col) == horde,
+            _ => false,
+        });
+
+        let count = game_state.colors[horde as usize].count_ones() as usize;
+        if count > MAX_HORDE {
+            errors.push(PositionError::TooManyPieces { player: horde, count });
+        }
+
+        // skipped by `validate_position` for want of a second king
+        let waiting = game_state.player_to_move.opponent();
+        if waiting != horde && is_player_in_check(game_state, waiting) {
+            errors.push(PositionError::OpponentInCheck { player: waiting });
+        }
+
+        into_result(errors)
+    }
+
+    // no draw by insufficient material: the king's side can always still
+    // capture what is left of the horde
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        for player in [Player::White, Player::Black] {
+            if game_state.colors[player as usize] == 0 {
+                return Some(GameResult::Win { winner: player.opponent(), reason: WinReason::HordeCaptured });
+            }
+        }
+
+        status_or_move_rule(self, game_state)
+    }
+}
@@ -0,0 +1,42 @@
+//! King of the Hill: standard chess, except that bringing the king to one
+//! of the four center squares also wins.
+
+use crate::bitboard::{bit, square, Bitboard};
+use crate::game_state::{GameState, PieceType, Player};
+use crate::movegen::generate_legal_moves;
+use crate::moves::MoveList;
+use crate::result::{GameResult, WinReason};
+use crate::variant::{status_or_move_rule, Variant};
+
+// d5, e5, d4 and e4
+const HILL: Bitboard = bit(square(3, 3)) | bit(square(3, 4)) | bit(square(4, 3)) | bit(square(4, 4));
+
+fn king_on_hill(game_state: &GameState) -> Option<Player> {
+    [Player::White, Player::Black].into_iter()
+        .find(|player| game_state.pieces_of(PieceType::King, *player) & HILL != 0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KingOfTheHill;
+
+impl Variant for KingOfTheHill {
+    fn name(&self) -> &'static str {
+        "kingofthehill"
+    }
+
+    fn legal_moves(&self, game_state: &GameState) -> MoveList {
+        match king_on_hill(game_state) {
+            Some(_) => MoveList::new(),
+            None => generate_legal_moves(game_state),
+        }
+    }
+
+    // no draw by insufficient material: a bare king can still walk to the hill
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        if let Some(winner) = king_on_hill(game_state) {
+            return Some(GameResult::Win { winner, reason: WinReason::KingOfTheHill });
+        }
+
+        status_or_move_rule(self, game_state)
+    }
+}
@@ -0,0 +1,53 @@
+//! Chess rules core: board representation, FEN parsing, legal move
+//! generation, perft and pluggable variant rules. The `xadreis` GUI binary
+//! is built on top of this.
+
+mod antichess;
+mod atomic;
+mod attacks;
+mod bitboard;
+mod chess960;
+mod crazyhouse;
+mod fen;
+mod game;
+mod game_state;
+mod horde;
+mod king_of_the_hill;
+mod magic;
+mod material;
+mod movegen;
+mod moves;
+mod perft;
+mod racing_kings;
+#[cfg(feature = "reference")]
+pub mod reference;
+mod result;
+mod see;
+mod three_check;
+mod validation;
+mod variant;
+mod zobrist;
+
+pub use antichess::Antichess;
+pub use atomic::Atomic;
+pub use attacks::{attackers, checkers, is_attacked, pinned, xray_attackers};
+pub use bitboard::SquareSet;
+pub use chess960::{chess960_position, double_chess960_position};
+pub use crazyhouse::Crazyhouse;
+pub use fen::{fen_to_game_state, game_state_to_fen, FenError, FenField, STARTING_FEN};
+pub use game::Game;
+pub use game_state::{CastlingSide, GameState, PieceType, Player};
+pub use horde::Horde;
+pub use king_of_the_hill::KingOfTheHill;
+pub use material::{has_insufficient_material, is_insufficient_material};
+pub use movegen::{generate_legal_moves, make_move, unmake_move, Undo};
+pub use moves::{Move, MoveKind, MoveList};
+pub use perft::{perft, perft_divide, variant_perft};
+pub use racing_kings::RacingKings;
+pub use result::{game_result, is_check, is_fifty_move_draw, is_seventy_five_move_draw, position_status, timeout_result,
+                 DrawReason, GameResult, PositionStatus, WinReason};
+pub use see::{piece_value, see};
+pub use three_check::ThreeCheck;
+pub use validation::{validate_position, PositionError};
+pub use variant::{Standard, Variant};
+pub use zobrist::compute_zobrist_key;
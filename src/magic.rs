@@ -0,0 +1,187 @@
+//! Rook and bishop attacks by table lookup. For every square, the pieces
+//! that can block a slider (its "mask") are hashed into an index into a
+//! shared table of precomputed attack sets. The hash is a magic multiply by
+//! default, or the BMI2 `pext` instruction with the `pext` cargo feature
+//! when building for a CPU that has it (e.g. `-C target-cpu=native`).
+//! Tables are built the first time a slider attack is asked for.
+
+use std::sync::OnceLock;
+
+use crate::bitboard::{bit, ray_attacks, row_col, square, Bitboard, BISHOP_DIRECTIONS, ROOK_DIRECTIONS};
+
+// Found by trying sparse random numbers until one mapped every occupancy of
+// the square's mask to an index without two different attack sets colliding.
+// Searching on startup takes seconds in debug builds, so they are kept here.
+const ROOK_MAGICS: [u64; 64] = [
+    0x0980008011400020, 0x8340004410002000, 0x0880200090008268, 0x0080080080100004,
+    0x8100110004020800, 0x0300010004000822, 0x08801a0029000080, 0x8100050001204882,
+    0x0844800081400320, 0x0804402010004000, 0x0108802003100480, 0x5c00808010000800,
+    0x0003001801001014, 0x0002000200041008, 0x0004008108042210, 0x0105000100009042,
+    0x0400808000400021, 0xc100404010002000, 0x0060008010002088, 0x0400808008001000,
+    0x4440808008000400, 0x1002008004000280, 0x40024400300d1248, 0x0010020000408104,
+    0x0101008200204200, 0x8020002040005000, 0x4100100080802000, 0x4008006a80100280,
+    0x0021008500100800, 0x5000040080800200, 0x0010040101000200, 0x6140004200008104,
+    0x4000400020800090, 0x2020002080804000, 0x0000408202002010, 0x0080100501000820,
+    0x0004040080800800, 0xa01a800200800400, 0x0014820104009008, 0x0008004082000401,
+    0x0029804004608010, 0x0140002000818048, 0x000c410420010014, 0x0020100008008080,
+    0x8000080004008080, 0x0082008004008002, 0x1002000801020004, 0x0010848505620004,
+    0x0000801840002080, 0x2002010c80403200, 0x9000114220010300, 0x0001000820100100,
+    0x000a800401080080, 0xc001400410200801, 0x4800480102300400, 0x1000010040840200,
+    0x0002800442162101, 0x4000810010204202, 0x0400201200084082, 0x8200210004081001,
+    0x1002001004200802, 0x0005000208040001, 0x0002002701ac0822, 0x000010250184004a,
+];
+
+const BISHOP_MAGICS: [u64; 64] = [
+    0x4014700208012480, 0x0220014200a10001, 0x8108084040806120, 0x200e0a02001a00c0,
+    0x4442021080243000, 0x4c00901008100050, 0x0800821110404840, 0x0000120222024003,
+    0x008a08a041020200, 0x000118460c0428a0, 0x0000314b06020022, 0x0804180481080025,
+    0x0000340520004880, 0x0440309004200008, 0x2108084402a01000, 0x0328024422082200,
+    0xc21200c004310c00, 0x460840040820c400, 0x0010004a02720020, 0x000800488a044044,
+    0x0025000a9040000a, 0x0009010600822104, 0x0021040400821002, 0x0801000a19010120,
+    0x0004048210915000, 0x0310222005242c05, 0x00544800101c8010, 0x2308080000820003,
+    0x2008820004010401, 0x0650110244208800, 0x0009324401080802, 0x01008022a6010c40,
+    0x8081200804200940, 0x300c040305200240, 0x0002062200240800, 0x2801400a00042200,
+    0x14090104000202a0, 0x05b0020080081048, 0x0a10010910020880, 0xc403020222060b00,
+    0x260d301250402080, 0x0000a80802088800, 0x0001008041023000, 0x0000020202016420,
+    0x0008540502140400, 0x0201101008400b80, 0x0104104202000058, 0x0002080101000031,
+    0x4200a20120200004, 0x0004208410090400, 0x10a0182508088000, 0x1814081420880800,
+    0x1008810850340611, 0x0081482248020610, 0x0009080800940000, 0x0060010240890800,
+    0x048a920802080500, 0x26000e9204900404, 0x200002a080482200, 0xac8018422020a808,
+    0x929a000440a50308, 0x41801040826c0110, 0x0140c01081010101, 0xc0a0012206040ea0,
+];
+
+#[derive(Clone, Copy, Default)]
+// `magic` and `shift` go unused when indexing with pext
+#[cfg_attr(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2"), allow(dead_code))]
+struct Magic {
+    mask: Bitboard,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl Magic {
+    #[cfg(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2"))]
+    #[inline(always)]
+    fn index(&self, occupied: Bitboard) -> usize {
+        // SAFETY: only compiled in when the target supports BMI2
+        self.offset + unsafe { std::arch::x86_64::_pext_u64(occupied, self.mask) } as usize
+    }
+
+    #[cfg(not(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2")))]
+    #[inline(always)]
+    fn index(&self, occupied: Bitboard) -> usize {
+        self.offset + ((occupied & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+}
+
+struct SlidingTables {
+    rook: [Magic; 64],
+    bishop: [Magic; 64],
+    attacks: Vec<Bitboard>,
+}
+
+static TABLES: OnceLock<SlidingTables> = OnceLock::new();
+
+fn tables() -> &'static SlidingTables {
+    TABLES.get_or_init(SlidingTables::new)
+}
+
+// squares whose occupancy changes the attacks from `sq`: every ray square
+// except the last one before the edge, which is attacked either way
+fn relevant_mask(sq: usize, directions: &[(isize, isize)]) -> Bitboard {
+    let mut mask = 0;
+    let (row, col) = row_col(sq);
+
+    for (dy, dx) in directions {
+        let mut o = row as isize + dy;
+        let mut p = col as isize + dx;
+        while (0..=7).contains(&(o + dy)) && (0..=7).contains(&(p + dx)) {
+            mask |= bit(square(o as usize, p as usize));
+            o += dy;
+            p += dx;
+        }
+    }
+
+    mask
+}
+
+impl SlidingTables {
+    fn new() -> SlidingTables {
+        let mut tables = SlidingTables {
+            rook: [Magic::default(); 64],
+            bishop: [Magic::default(); 64],
+            attacks: vec![],
+        };
+
+        for sq in 0..64 {
+            tables.rook[sq] = tables.add_square(sq, &ROOK_DIRECTIONS, ROOK_MAGICS[sq]);
+            tables.bishop[sq] = tables.add_square(sq, &BISHOP_DIRECTIONS, BISHOP_MAGICS[sq]);
+        }
+
+        tables
+    }
+
+    // fills this square's slice of the attack table and returns how to index it
+    fn add_square(&mut self, sq: usize, directions: &[(isize, isize)], magic: u64) -> Magic {
+        let mask = relevant_mask(sq, directions);
+        let bits = mask.count_ones();
+        let magic = Magic { mask, magic, shift: 64 - bits, offset: self.attacks.len() };
+        self.attacks.resize(magic.offset + (1 << bits), 0);
+
+        // every subset of the mask, walking the rays once for each
+        let mut occupied: Bitboard = 0;
+        loop {
+            let attacks = ray_attacks(sq, occupied, directions);
+            let entry = &mut self.attacks[magic.index(occupied)];
+            // slider attacks are never empty, so 0 is an unused entry
+            debug_assert!(*entry == 0 || *entry == attacks, "bad magic for square {}", sq);
+            *entry = attacks;
+
+            occupied = occupied.wrapping_sub(mask) & mask;
+            if occupied == 0 {
+                break;
+            }
+        }
+
+        magic
+    }
+}
+
+pub(crate) fn rook_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    let tables = tables();
+    tables.attacks[tables.rook[square].index(occupied)]
+}
+
+pub(crate) fn bishop_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    let tables = tables();
+    tables.attacks[tables.bishop[square].index(occupied)]
+}
+
+pub(crate) fn queen_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
+
+/// Squares strictly between `a` and `b` when they share a line, file or
+/// diagonal; empty otherwise.
+pub(crate) fn between(a: usize, b: usize) -> Bitboard {
+    if rook_attacks(a, 0) & bit(b) != 0 {
+        rook_attacks(a, bit(b)) & rook_attacks(b, bit(a))
+    } else if bishop_attacks(a, 0) & bit(b) != 0 {
+        bishop_attacks(a, bit(b)) & bishop_attacks(b, bit(a))
+    } else {
+        0
+    }
+}
+
+/// The whole line, file or diagonal through `a` and `b`, edge to edge;
+/// empty if they are not aligned.
+pub(crate) fn line(a: usize, b: usize) -> Bitboard {
+    if rook_attacks(a, 0) & bit(b) != 0 {
+        (rook_attacks(a, 0) & rook_attacks(b, 0)) | bit(a) | bit(b)
+    } else if bishop_attacks(a, 0) & bit(b) != 0 {
+        (bishop_attacks(a, 0) & bishop_attacks(b, 0)) | bit(a) | bit(b)
+    } else {
+        0
+    }
+}
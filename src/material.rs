@@ -0,0 +1,78 @@
+use crate::game_state::{GameState, PieceType, Player};
+
+/// Pieces of one side, counted by type, plus the square colours its bishops stand on.
+struct Material {
+    counts: [usize; 7],
+    bishops_on_light: bool,
+    bishops_on_dark: bool,
+}
+
+impl Material {
+    fn of(game_state: &GameState, player: Player) -> Material {
+        let mut material = Material { counts: [0; 7], bishops_on_light: false, bishops_on_dark: false };
+        for row in 0..8 {
+            for col in 0..8 {
+                let (piece, owner) = game_state.piece_at(row, col);
+                if owner != player {
+                    continue;
+                }
+                material.counts[piece as usize] += 1;
+                if piece == PieceType::Bishop {
+                    // a8, at (0,0), is a light square
+                    if (row + col) % 2 == 0 {
+                        material.bishops_on_light = true;
+                    } else {
+                        material.bishops_on_dark = true;
+                    }
+                }
+            }
+        }
+        material
+    }
+
+    fn count(&self, piece: PieceType) -> usize {
+        self.counts[piece as usize]
+    }
+
+    fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+/// Whether `player` can not checkmate by any sequence of legal moves, even
+/// with the opponent's help. This is what decides a game lost on time: the
+/// flag only loses if the opponent could still have mated.
+pub fn has_insufficient_material(game_state: &GameState, player: Player) -> bool {
+    let us = Material::of(game_state, player);
+    let them = Material::of(game_state, player.opponent());
+
+    if us.count(PieceType::Pawn) + us.count(PieceType::Rook) + us.count(PieceType::Queen) > 0 {
+        return false;
+    }
+
+    // a lone knight can only mate if the opponent has pieces to block its own
+    // king in with; queens can not be forced to do that
+    if us.count(PieceType::Knight) > 0 {
+        return us.total() <= 2 &&
+            them.total() == them.count(PieceType::King) + them.count(PieceType::Queen);
+    }
+
+    // bishops need a bishop of the other colour, or an opposing pawn or knight
+    // to block the king in, before mate is possible
+    if us.count(PieceType::Bishop) > 0 {
+        let light = us.bishops_on_light || them.bishops_on_light;
+        let dark = us.bishops_on_dark || them.bishops_on_dark;
+        return !(light && dark) &&
+            them.count(PieceType::Pawn) == 0 &&
+            them.count(PieceType::Knight) == 0;
+    }
+
+    true
+}
+
+/// A dead position by material: neither side can ever checkmate, as in
+/// K v K, K+minor v K, or kings and bishops all on squares of one colour.
+pub fn is_insufficient_material(game_state: &GameState) -> bool {
+    has_insufficient_material(game_state, Player::White) &&
+        has_insufficient_material(game_state, Player::Black)
+}
@@ -0,0 +1,478 @@
+use crate::attacks::{attackers_to, is_player_in_check, pinned_pieces};
+use crate::bitboard::{bit, row_col, square, Squares, Bitboard, KING_ATTACKS, KNIGHT_ATTACKS, PAWN_ATTACKS};
+use crate::game_state::{CastlingRights, CastlingSide, GameState, PieceType, Player, Square};
+use crate::magic::{between, bishop_attacks, line, queen_attacks, rook_attacks};
+use crate::moves::{Move, MoveKind, MoveList};
+use crate::zobrist::{castling_key, compute_zobrist_key, en_passant_key, side_to_move_key};
+
+fn swap_player_turn(game_state: &mut GameState) {
+    match game_state.player_to_move {
+        Player::Black => { game_state.player_to_move = Player::White; },
+        Player::White => { game_state.player_to_move = Player::Black; },
+        Player::None => { panic!("Invalid player_to_move"); }
+    };
+    game_state.zobrist_key ^= side_to_move_key();
+}
+
+// castling rights and en passant target are hashed as a whole, so they are
+// XORed out before a move changes them and back in afterwards
+fn toggle_rights_key(game_state: &mut GameState) {
+    game_state.zobrist_key ^= castling_key(game_state.castling_rights) ^
+                              en_passant_key(game_state.en_passant_target);
+}
+
+/// Where the king and rook end up when the king on `king` castles with the
+/// rook on `rook`: the g- and f-file on the rook's right, the c- and d-file
+/// on its left, whatever files they started on.
+pub(crate) fn castling_destinations(king: (usize, usize), rook: (usize, usize)) -> ((usize, usize), (usize, usize)) {
+    let row = king.0;
+    if rook.1 > king.1 { ((row, 6), (row, 5)) } else { ((row, 2), (row, 3)) }
+}
+
+/// What `make_move` overwrote, so that `unmake_move` can restore it.
+#[derive(Debug, Clone, Copy)]
+pub struct Undo {
+    // piece removed by the move; for en passant this is the passed pawn
+    captured: Square,
+    castling_rights: CastlingRights,
+    en_passant_target: Option<(u8, u8)>,
+    halfmove_counter: u16,
+    promoted: Bitboard,
+}
+
+/// Plays `mv` on `game_state`. The move is expected to come from
+/// `generate_legal_moves` for the same position. The returned `Undo` takes
+/// the move back with `unmake_move`.
+pub fn make_move(game_state: &mut GameState, mv: Move) -> Undo {
+    if let Some(piece) = mv.drop_piece() {
+        return make_drop(game_state, piece, mv.to());
+    }
+
+    let (from, to) = (mv.from(), mv.to());
+    let (moving_piece, player) = game_state.piece_at(from.0, from.1);
+    // castling is encoded as the king taking its own rook, which stays on the board
+    let castling = mv.kind() == MoveKind::Castling;
+
+    let mut undo = Undo {
+        captured: if castling { Square(0) } else { game_state.board[to.0][to.1] },
+        castling_rights: game_state.castling_rights,
+        en_passant_target: game_state.en_passant_target,
+        halfmove_counter: game_state.halfmove_counter,
+        promoted: game_state.promoted,
+    };
+    toggle_rights_key(game_state);
+
+    if castling {
+        let (king_to, rook_to) = castling_destinations(from, to);
+
+        game_state.set_piece_at(from.0, from.1, PieceType::None, Player::None);
+        game_state.set_piece_at(to.0, to.1, PieceType::None, Player::None);
+        game_state.set_piece_at(king_to.0, king_to.1, PieceType::King, player);
+        game_state.set_piece_at(rook_to.0, rook_to.1, PieceType::Rook, player);
+    } else {
+        game_state.set_piece_at(to.0, to.1, moving_piece, player);
+        game_state.set_piece_at(from.0, from.1, PieceType::None, Player::None);
+    }
+
+    match mv.kind() {
+        MoveKind::Quiet | MoveKind::DoublePush | MoveKind::Capture | MoveKind::Castling | MoveKind::Drop => {},
+        MoveKind::Promotion | MoveKind::CapturePromotion => {
+            game_state.set_piece_at(to.0, to.1, mv.promotion().unwrap(), player);
+        },
+        MoveKind::EnPassant => {
+            let captured_at = (from.0, to.1);
+
+            undo.captured = game_state.board[captured_at.0][captured_at.1];
+            game_state.set_piece_at(captured_at.0, captured_at.1, PieceType::None, Player::None);
+        },
+    }
+
+    // with pockets, the capturer pockets what it took, as a pawn if it had
+    // promoted, and promoted pieces stay marked wherever they go
+    if game_state.pockets.is_some() {
+        let captured = PieceType::try_from(undo.captured.get_piece()).unwrap();
+        let captured_at = if mv.kind() == MoveKind::EnPassant { square(from.0, to.1) } else { square(to.0, to.1) };
+        let mut promoted = game_state.promoted;
+        if captured != PieceType::None {
+            let pocketed = if promoted & bit(captured_at) != 0 { PieceType::Pawn } else { captured };
+            game_state.add_to_pocket(player, pocketed);
+            promoted &= !bit(captured_at);
+        }
+        if promoted & bit(square(from.0, from.1)) != 0 {
+            promoted ^= bit(square(from.0, from.1)) | bit(square(to.0, to.1));
+        }
+        if mv.promotion().is_some() {
+            promoted |= bit(square(to.0, to.1));
+        }
+        game_state.set_promoted(promoted);
+    }
+
+    // a Horde pawn double-stepping from the first rank can't be taken en passant
+    game_state.en_passant_target = None;
+    if mv.kind() == MoveKind::DoublePush && from.0 != player.home_row() {
+        game_state.en_passant_target = Some((to.0 as u8, to.1 as u8));
+    }
+
+    // moving the king gives up castling; so does a rook leaving its
+    // starting square or being captured there
+    let rights = &mut game_state.castling_rights;
+    if moving_piece == PieceType::King {
+        rights.set_rook_file(player, CastlingSide::Kingside, None);
+        rights.set_rook_file(player, CastlingSide::Queenside, None);
+    }
+    for owner in [Player::White, Player::Black] {
+        for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+            if let Some(file) = rights.rook_file(owner, side) {
+                let rook = (owner.home_row(), file);
+                if from == rook || to == rook {
+                    rights.set_rook_file(owner, side, None);
+                }
+            }
+        }
+    }
+
+    finish_move(game_state, mv.is_capture() || moving_piece == PieceType::Pawn);
+
+    undo
+}
+
+// a drop captures nothing and moves nothing, so only the pocket, the en
+// passant target and the clocks change besides the piece appearing
+fn make_drop(game_state: &mut GameState, piece: PieceType, (row, col): (usize, usize)) -> Undo {
+    let undo = Undo {
+        captured: Square(0),
+        castling_rights: game_state.castling_rights,
+        en_passant_target: game_state.en_passant_target,
+        halfmove_counter: game_state.halfmove_counter,
+        promoted: game_state.promoted,
+    };
+    toggle_rights_key(game_state);
+
+    let player = game_state.player_to_move;
+    game_state.remove_from_pocket(player, piece);
+    game_state.set_piece_at(row, col, piece, player);
+    game_state.en_passant_target = None;
+
+    finish_move(game_state, false);
+
+    undo
+}
+
+// advances the clocks and hands the move to the other side
+fn finish_move(game_state: &mut GameState, resets_halfmove_clock: bool) {
+    // the halfmove clock counts plies since the last capture or pawn move
+    if resets_halfmove_clock {
+        game_state.halfmove_counter = 0;
+    } else {
+        game_state.halfmove_counter += 1;
+    };
+    if game_state.player_to_move == Player::Black {
+        game_state.fullmove_counter += 1;
+    }
+
+    toggle_rights_key(game_state);
+    swap_player_turn(game_state);
+    debug_assert_eq!(game_state.zobrist_key, compute_zobrist_key(game_state));
+}
+
+/// Takes back `mv`, restoring `game_state` exactly as it was before
+/// `make_move` returned `undo`.
+pub fn unmake_move(game_state: &mut GameState, mv: Move, undo: Undo) {
+    swap_player_turn(game_state);
+    toggle_rights_key(game_state);
+
+    let (from, to) = (mv.from(), mv.to());
+    let player = game_state.player_to_move;
+
+    if let Some(piece) = mv.drop_piece() {
+        game_state.set_piece_at(to.0, to.1, PieceType::None, Player::None);
+        game_state.add_to_pocket(player, piece);
+    } else if mv.kind() == MoveKind::Castling {
+        let (king_to, rook_to) = castling_destinations(from, to);
+
+        game_state.set_piece_at(king_to.0, king_to.1, PieceType::None, Player::None);
+        game_state.set_piece_at(rook_to.0, rook_to.1, PieceType::None, Player::None);
+        game_state.set_piece_at(from.0, from.1, PieceType::King, player);
+        game_state.set_piece_at(to.0, to.1, PieceType::Rook, player);
+    } else {
+        let (moving_piece, captured_at) = match mv.kind() {
+            MoveKind::Promotion | MoveKind::CapturePromotion => (PieceType::Pawn, to),
+            MoveKind::EnPassant => (PieceType::Pawn, (from.0, to.1)),
+            _ => (game_state.piece_at(to.0, to.1).0, to),
+        };
+
+        let captured_piece = PieceType::try_from(undo.captured.get_piece()).unwrap();
+        let captured_owner = Player::try_from(undo.captured.get_owner()).unwrap();
+        game_state.set_piece_at(to.0, to.1, PieceType::None, Player::None);
+        game_state.set_piece_at(captured_at.0, captured_at.1, captured_piece, captured_owner);
+        game_state.set_piece_at(from.0, from.1, moving_piece, player);
+
+        if captured_piece != PieceType::None && game_state.pockets.is_some() {
+            let promoted = undo.promoted & bit(square(captured_at.0, captured_at.1)) != 0;
+            game_state.remove_from_pocket(player, if promoted { PieceType::Pawn } else { captured_piece });
+        }
+    }
+
+    game_state.set_promoted(undo.promoted);
+    game_state.castling_rights = undo.castling_rights;
+    game_state.en_passant_target = undo.en_passant_target;
+    game_state.halfmove_counter = undo.halfmove_counter;
+    if player == Player::Black {
+        game_state.fullmove_counter -= 1;
+    }
+    toggle_rights_key(game_state);
+    debug_assert_eq!(game_state.zobrist_key, compute_zobrist_key(game_state));
+}
+
+// moves to the squares in `targets`, captures where there are `enemies`
+fn push_moves(moves: &mut MoveList, from: usize, targets: Bitboard, enemies: Bitboard) {
+    for to in Squares(targets & !enemies) {
+        moves.push(Move::new(from, to));
+    }
+    for to in Squares(targets & enemies) {
+        moves.push(Move::new_capture(from, to));
+    }
+}
+
+fn push_pawn_move(moves: &mut MoveList, from: usize, to: usize, capture: bool, promotion_row: usize) {
+    match (row_col(to).0 == promotion_row, capture) {
+        (true, false) => {
+            for pt in PieceType::iterator() {
+                moves.push(Move::new_promotion(from, to, pt));
+            }
+        },
+        (true, true) => {
+            for pt in PieceType::iterator() {
+                moves.push(Move::new_capture_promotion(from, to, pt));
+            }
+        },
+        (false, false) => moves.push(Move::new(from, to)),
+        (false, true) => moves.push(Move::new_capture(from, to)),
+    }
+}
+
+// what a non-king move must satisfy to be legal, worked out once per position
+struct Constraints {
+    king: Option<usize>,
+    // when in check, the checker and the squares between it and the king;
+    // every square but our own otherwise
+    target: Bitboard,
+    pinned: Bitboard,
+}
+
+impl Constraints {
+    fn new(game_state: &GameState, player: Player) -> Constraints {
+        let own = game_state.colors[player as usize];
+        let enemies = game_state.colors[player.opponent() as usize];
+        let king = match game_state.king_square(player) {
+            Some(king) => king,
+            None => return Constraints { king: None, target: !own, pinned: 0 },
+        };
+
+        let occupied = game_state.occupied();
+        let checkers = attackers_to(game_state, king, occupied) & enemies;
+        let target = match checkers.count_ones() {
+            0 => !own,
+            1 => checkers | between(king, checkers.trailing_zeros() as usize),
+            // only the king can answer a double check
+            _ => 0,
+        };
+
+        Constraints { king: Some(king), target, pinned: pinned_pieces(game_state, king) }
+    }
+
+    // the subset of `targets` a piece on `from` may move to
+    fn allowed(&self, from: usize, targets: Bitboard) -> Bitboard {
+        match self.king {
+            Some(king) if self.pinned & bit(from) != 0 => targets & self.target & line(king, from),
+            _ => targets & self.target,
+        }
+    }
+}
+
+fn generate_pawn_moves(game_state: &GameState, player: Player, constraints: &Constraints, moves: &mut MoveList) {
+    let occupied = game_state.occupied();
+    let enemies = game_state.colors[player.opponent() as usize];
+    // square offset of one step forward
+    let (forward, start_row, promotion_row) = if player == Player::White { (-8isize, 6, 0) } else { (8, 1, 7) };
+
+    for from in Squares(game_state.pieces_of(PieceType::Pawn, player)) {
+        let (row, col) = row_col(from);
+
+        let mut targets = PAWN_ATTACKS[player as usize][from] & enemies;
+        let one = (from as isize + forward) as usize;
+        if occupied & bit(one) == 0 {
+            targets |= bit(one);
+
+            let two = (one as isize + forward) as usize;
+            // Horde pawns may also double-step from the first rank
+            if (row == start_row || row == player.home_row()) && occupied & bit(two) == 0 &&
+               constraints.allowed(from, bit(two)) != 0 {
+                moves.push(Move::new_double_push(from, two));
+            }
+        }
+        for to in Squares(constraints.allowed(from, targets)) {
+            push_pawn_move(moves, from, to, enemies & bit(to) != 0, promotion_row);
+        }
+
+        if let Some((t_row, t_col)) = game_state.en_passant_target {
+            let (t_row, t_col) = (t_row as usize, t_col as usize);
+            if t_row == row && t_col.abs_diff(col) == 1 {
+                let to = (from as isize + forward) as usize - col + t_col;
+                let captured = square(t_row, t_col);
+                let legal = constraints.king.is_none_or(|king| {
+                    is_en_passant_legal(game_state, player, king, from, to, captured)
+                });
+                if legal {
+                    moves.push(Move::new_en_passant(from, to));
+                }
+            }
+        }
+    }
+}
+
+// en passant removes two pieces from a line at once, so pins don't describe
+// it; instead look at the king after the capture
+fn is_en_passant_legal(game_state: &GameState, player: Player, king: usize, from: usize, to: usize,
+                       captured: usize) -> bool {
+    let occupied = (game_state.occupied() ^ bit(from) ^ bit(captured)) | bit(to);
+    let enemies = game_state.colors[player.opponent() as usize] & !bit(captured);
+    attackers_to(game_state, king, occupied) & enemies == 0
+}
+
+fn generate_piece_moves(game_state: &GameState, player: Player, constraints: &Constraints, moves: &mut MoveList) {
+    let occupied = game_state.occupied();
+    let enemies = game_state.colors[player.opponent() as usize];
+
+    for from in Squares(game_state.pieces_of(PieceType::Knight, player)) {
+        push_moves(moves, from, constraints.allowed(from, KNIGHT_ATTACKS[from]), enemies);
+    }
+    for from in Squares(game_state.pieces_of(PieceType::Bishop, player)) {
+        push_moves(moves, from, constraints.allowed(from, bishop_attacks(from, occupied)), enemies);
+    }
+    for from in Squares(game_state.pieces_of(PieceType::Rook, player)) {
+        push_moves(moves, from, constraints.allowed(from, rook_attacks(from, occupied)), enemies);
+    }
+    for from in Squares(game_state.pieces_of(PieceType::Queen, player)) {
+        push_moves(moves, from, constraints.allowed(from, queen_attacks(from, occupied)), enemies);
+    }
+}
+
+fn generate_king_moves(game_state: &GameState, player: Player, moves: &mut MoveList) {
+    let enemies = game_state.colors[player.opponent() as usize];
+
+    for from in Squares(game_state.pieces_of(PieceType::King, player)) {
+        // the king must not hide behind itself from a slider
+        let occupied = game_state.occupied() ^ bit(from);
+        let targets = KING_ATTACKS[from] & !game_state.colors[player as usize];
+        for to in Squares(targets) {
+            if attackers_to(game_state, to, occupied) & enemies == 0 {
+                push_moves(moves, from, bit(to), enemies);
+            }
+        }
+    }
+}
+
+// the king may not castle out of, through or into check. Squares are
+// checked with both pieces lifted, as in Chess960 the rook may have been
+// shielding the king's destination. Without `check_attacks` only the
+// squares being empty matters.
+fn generate_castling_moves(game_state: &GameState, player: Player, check_attacks: bool, moves: &mut MoveList) {
+    let rights = game_state.castling_rights;
+    let row = player.home_row();
+    let king = match game_state.king_square(player) {
+        Some(king) if row_col(king).0 == row => king,
+        _ => return,
+    };
+    if !(rights.allowed(player, CastlingSide::Kingside) || rights.allowed(player, CastlingSide::Queenside)) ||
+       (check_attacks && is_player_in_check(game_state, player)) {
+        return;
+    }
+
+    let occupied = game_state.occupied();
+    let enemies = game_state.colors[player.opponent() as usize];
+
+    for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+        let rook = match rights.rook_file(player, side) {
+            Some(file) => square(row, file),
+            None => continue,
+        };
+        if game_state.pieces_of(PieceType::Rook, player) & bit(rook) == 0 {
+            continue;
+        }
+
+        let (king_to, rook_to) = castling_destinations(row_col(king), row_col(rook));
+        let (king_to, rook_to) = (square(king_to.0, king_to.1), square(rook_to.0, rook_to.1));
+        let king_path = between(king, king_to) | bit(king_to);
+        let rook_path = between(rook, rook_to) | bit(rook_to);
+        if (king_path | rook_path) & occupied & !bit(king) & !bit(rook) != 0 {
+            continue;
+        }
+
+        let lifted = occupied ^ bit(king) ^ bit(rook);
+        if !check_attacks || Squares(king_path).all(|sq| attackers_to(game_state, sq, lifted) & enemies == 0) {
+            moves.push(Move::new_castling(king, rook));
+        }
+    }
+}
+
+// pieces from the pocket may go on any empty square that the side to move
+// could also move a piece to, which when in check means blocking it;
+// pawns never go on the first or last rank
+fn generate_drops(game_state: &GameState, player: Player, constraints: &Constraints, moves: &mut MoveList) {
+    let pockets = match game_state.pockets {
+        Some(pockets) => pockets[player as usize],
+        None => return,
+    };
+    let targets = constraints.target & !game_state.occupied();
+    const BACK_RANKS: Bitboard = 0xff | 0xff << 56;
+
+    for piece in [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen] {
+        if pockets[piece as usize] == 0 {
+            continue;
+        }
+        let targets = if piece == PieceType::Pawn { targets & !BACK_RANKS } else { targets };
+        for to in Squares(targets) {
+            moves.push(Move::new_drop(piece, to));
+        }
+    }
+}
+
+/// All legal moves for the side to move, including drops when the position
+/// has pockets. Checkers and pinned pieces are found once, so every move is
+/// generated already legal.
+pub fn generate_legal_moves(game_state: &GameState) -> MoveList {
+    let player = game_state.player_to_move;
+    let constraints = Constraints::new(game_state, player);
+    let mut moves = MoveList::new();
+
+    if constraints.target != 0 {
+        generate_pawn_moves(game_state, player, &constraints, &mut moves);
+        generate_piece_moves(game_state, player, &constraints, &mut moves);
+        generate_drops(game_state, player, &constraints, &mut moves);
+    }
+    generate_king_moves(game_state, player, &mut moves);
+    generate_castling_moves(game_state, player, true, &mut moves);
+
+    moves
+}
+
+/// Moves that follow how the pieces move, whether or not they leave the
+/// king in check, for variants with their own idea of what is legal.
+/// Castling only needs the squares between king and rook to be empty.
+pub(crate) fn generate_pseudo_legal_moves(game_state: &GameState) -> MoveList {
+    let player = game_state.player_to_move;
+    let own = game_state.colors[player as usize];
+    let enemies = game_state.colors[player.opponent() as usize];
+    let unconstrained = Constraints { king: None, target: !own, pinned: 0 };
+    let mut moves = MoveList::new();
+
+    generate_pawn_moves(game_state, player, &unconstrained, &mut moves);
+    generate_piece_moves(game_state, player, &unconstrained, &mut moves);
+    for from in Squares(game_state.pieces_of(PieceType::King, player)) {
+        push_moves(&mut moves, from, KING_ATTACKS[from] & !own, enemies);
+    }
+    generate_castling_moves(game_state, player, false, &mut moves);
+
+    moves
+}
@@ -0,0 +1,240 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::bitboard::row_col;
+use crate::game_state::PieceType;
+
+/// What kind of move a `Move` is, known without looking at the board.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MoveKind {
+    /// A move to an empty square.
+    Quiet,
+    /// A pawn moving two squares forward.
+    DoublePush,
+    Capture,
+    /// The captured pawn is beside the origin square, not on the destination.
+    EnPassant,
+    /// The king and rook swap sides; the destination is the rook's square.
+    Castling,
+    /// A piece from the pocket is put on the destination; there is no origin.
+    Drop,
+    /// A pawn moving to an empty square on the last rank.
+    Promotion,
+    /// A pawn capturing on the last rank.
+    CapturePromotion,
+}
+
+// 4-bit codes in the top of a packed move; promotions take the codes from
+// PROMOTION on and capture-promotions those from CAPTURE_PROMOTION on, in
+// the order of PROMOTIONS
+const QUIET: u16 = 0;
+const DOUBLE_PUSH: u16 = 1;
+const CAPTURE: u16 = 2;
+const EN_PASSANT: u16 = 3;
+const CASTLING: u16 = 4;
+const DROP: u16 = 5;
+const PROMOTION: u16 = 6;
+const CAPTURE_PROMOTION: u16 = PROMOTION + PROMOTIONS.len() as u16;
+
+// a king only in Antichess
+const PROMOTIONS: [PieceType; 5] =
+    [PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+
+/// A move packed into 16 bits: destination square in bits 0-5, origin
+/// square in bits 6-11 and its `MoveKind` in bits 12-15, which for
+/// promotions includes the piece promoted to. Squares are numbered
+/// `line * 8 + column`. Drops keep the dropped `PieceType` in place of the
+/// origin.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move(u16);
+
+impl Move {
+    const fn pack(from: usize, to: usize, code: u16) -> Move {
+        Move(code << 12 | (from as u16) << 6 | to as u16)
+    }
+
+    /// A move to an empty square with nothing special about it.
+    pub(crate) const fn new(from: usize, to: usize) -> Move {
+        Move::pack(from, to, QUIET)
+    }
+
+    pub(crate) const fn new_double_push(from: usize, to: usize) -> Move {
+        Move::pack(from, to, DOUBLE_PUSH)
+    }
+
+    pub(crate) const fn new_capture(from: usize, to: usize) -> Move {
+        Move::pack(from, to, CAPTURE)
+    }
+
+    pub(crate) fn new_promotion(from: usize, to: usize, piece: PieceType) -> Move {
+        Move::pack(from, to, PROMOTION + promotion_index(piece))
+    }
+
+    pub(crate) fn new_capture_promotion(from: usize, to: usize, piece: PieceType) -> Move {
+        Move::pack(from, to, CAPTURE_PROMOTION + promotion_index(piece))
+    }
+
+    pub(crate) const fn new_en_passant(from: usize, to: usize) -> Move {
+        Move::pack(from, to, EN_PASSANT)
+    }
+
+    /// Castling, given as the squares of the king and of the rook it
+    /// castles with, as if the king captured its own rook.
+    pub(crate) const fn new_castling(from: usize, to: usize) -> Move {
+        Move::pack(from, to, CASTLING)
+    }
+
+    /// Putting `piece` from the pocket on `to`.
+    pub(crate) const fn new_drop(piece: PieceType, to: usize) -> Move {
+        Move::pack(piece as usize, to, DROP)
+    }
+
+    pub(crate) const fn origin(&self) -> usize {
+        (self.0 >> 6 & 0x3f) as usize
+    }
+
+    pub(crate) const fn destination(&self) -> usize {
+        (self.0 & 0x3f) as usize
+    }
+
+    /// What kind of move this is.
+    pub fn kind(&self) -> MoveKind {
+        match self.0 >> 12 {
+            QUIET => MoveKind::Quiet,
+            DOUBLE_PUSH => MoveKind::DoublePush,
+            CAPTURE => MoveKind::Capture,
+            EN_PASSANT => MoveKind::EnPassant,
+            CASTLING => MoveKind::Castling,
+            DROP => MoveKind::Drop,
+            code if code < CAPTURE_PROMOTION => MoveKind::Promotion,
+            _ => MoveKind::CapturePromotion,
+        }
+    }
+
+    /// Whether this move takes a piece, en passant included.
+    pub fn is_capture(&self) -> bool {
+        matches!(self.kind(), MoveKind::Capture | MoveKind::EnPassant | MoveKind::CapturePromotion)
+    }
+
+    /// Origin square as (line, column). Drops have none, so for them this
+    /// is the destination.
+    pub fn from(&self) -> (usize, usize) {
+        match self.kind() {
+            MoveKind::Drop => self.to(),
+            _ => row_col(self.origin()),
+        }
+    }
+
+    /// Destination square as (line, column). For castling this is the
+    /// square of the rook, which works for Chess960 as well.
+    pub fn to(&self) -> (usize, usize) {
+        row_col(self.destination())
+    }
+
+    /// Piece a pawn promotes to, if this move is a promotion.
+    pub fn promotion(&self) -> Option<PieceType> {
+        match self.kind() {
+            MoveKind::Promotion => Some(PROMOTIONS[((self.0 >> 12) - PROMOTION) as usize]),
+            MoveKind::CapturePromotion => Some(PROMOTIONS[((self.0 >> 12) - CAPTURE_PROMOTION) as usize]),
+            _ => None,
+        }
+    }
+
+    /// Piece put on the board, if this move is a drop.
+    pub fn drop_piece(&self) -> Option<PieceType> {
+        match self.kind() {
+            MoveKind::Drop => Some(PieceType::try_from(self.origin() as u8).unwrap()),
+            _ => None,
+        }
+    }
+}
+
+fn promotion_index(piece: PieceType) -> u16 {
+    PROMOTIONS.iter().position(|p| *p == piece).expect("not a promotion piece") as u16
+}
+
+// TODO: change to using modern chess notation
+// origin and destination, plus the promotion piece, like "e7e8q"; drops
+// are written with the piece letter, like "N@f3". Castling from the
+// standard squares is written as the king's move, like "e1g1", and any
+// other castling as the king taking its rook, as in Chess960.
+impl fmt::Debug for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (from, mut to) = (self.from(), self.to());
+        if self.kind() == MoveKind::Castling && from.1 == 4 && (to.1 == 0 || to.1 == 7) {
+            to.1 = if to.1 == 7 { 6 } else { 2 };
+        }
+        let from_file = ((from.1 as u8) + b'a') as char;
+        let to_file = ((to.1 as u8) + b'a') as char;
+        if let Some(piece) = self.drop_piece() {
+            return write!(f, "{}@{}{}", piece.letter(), to_file, (7 - to.0) + 1);
+        }
+        write!(f, "{}{}{}{}", from_file, (7 - from.0) + 1, to_file, (7 - to.0) + 1)?;
+        if let Some(piece) = self.promotion() {
+            write!(f, "{}", piece.letter().to_ascii_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+/// Most moves a chess position can have is 218; Crazyhouse drops add up to
+/// five pieces on each empty square, so this always suffices.
+const MAX_MOVES: usize = 512;
+
+/// A fixed-capacity list of moves kept on the stack, so generating moves
+/// never allocates. Derefs to a slice of the moves pushed so far.
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    pub fn new() -> MoveList {
+        MoveList { moves: [Move(0); MAX_MOVES], len: 0 }
+    }
+
+    pub(crate) fn push(&mut self, mv: Move) {
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> MoveList {
+        MoveList::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = std::iter::Take<std::array::IntoIter<Move, MAX_MOVES>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter().take(self.len)
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
@@ -0,0 +1,65 @@
+use crate::game_state::GameState;
+use crate::movegen::{generate_legal_moves, make_move, unmake_move};
+use crate::variant::Variant;
+
+/// Counts the leaf nodes `n` plies deep. `results[level]` accumulates the
+/// number of moves generated at each level; untouched levels stay at -1.
+pub fn perft(results: &mut [isize; 8], game_state: &GameState, n: usize) -> usize {
+    let mut scratch = *game_state;
+    perft_imp(results, &mut scratch, n, 1, false)
+}
+
+/// Same as `perft`, but prints the node count below each root move.
+pub fn perft_divide(results: &mut [isize; 8], game_state: &GameState, n: usize) -> usize {
+    let mut scratch = *game_state;
+    perft_imp(results, &mut scratch, n, 1, true)
+}
+
+fn perft_imp(results: &mut [isize; 8], game_state: &mut GameState, n: usize, level: usize, divide: bool) -> usize {
+    if n == 0 {
+        return 1;
+    }
+
+    let mut count = 0;
+    let moves = generate_legal_moves(game_state);
+
+    if results[level] == -1 {
+        results[level] = 0;
+    }
+    results[level] += moves.len() as isize;
+
+    // every generated move is legal, so the last ply needs no playing out
+    if n == 1 && !(level == 1 && divide) {
+        return moves.len();
+    }
+
+    for mv in &moves {
+        let undo = make_move(game_state, *mv);
+        let nodes = perft_imp(results, game_state, n - 1, level + 1, divide);
+        unmake_move(game_state, *mv, undo);
+        if level == 1 && divide { println!("{:?} {}", mv, nodes); }
+        count += nodes;
+    }
+
+    count
+}
+
+/// Counts the leaf nodes `n` plies deep under the rules of `variant`. Each
+/// move is played on a copy of the position, since variants need not be
+/// able to take moves back.
+pub fn variant_perft(variant: &dyn Variant, game_state: &GameState, n: usize) -> usize {
+    if n == 0 {
+        return 1;
+    }
+
+    let moves = variant.legal_moves(game_state);
+    if n == 1 {
+        return moves.len();
+    }
+
+    moves.iter().map(|mv| {
+        let mut next = *game_state;
+        variant.play(&mut next, *mv);
+        variant_perft(variant, &next, n - 1)
+    }).sum()
+}
@@ -0,0 +1,113 @@
+//! Racing Kings: there are no pawns and no checks, and the first king to
+//! reach the eighth rank wins. White moves first, so when White gets there
+//! Black has one more move, and draws by getting there too.
+
+use crate::attacks::is_player_in_check;
+use crate::bitboard::{bit, Bitboard};
+use crate::game_state::{CastlingRights, GameState, PieceType, Player};
+use crate::movegen::{generate_legal_moves, make_move};
+use crate::moves::MoveList;
+use crate::result::{DrawReason, GameResult, WinReason};
+use crate::validation::{into_result, position_errors_except, PositionError};
+use crate::variant::{status_or_move_rule, Variant};
+
+// a8 to h8
+const EIGHTH_RANK: Bitboard = 0xff;
+
+// legal moves that don't give check, whether or not the race is over
+fn racing_moves(game_state: &GameState) -> MoveList {
+    let opponent = game_state.player_to_move.opponent();
+    let mut moves = MoveList::new();
+    for mv in generate_legal_moves(game_state) {
+        let mut after = *game_state;
+        make_move(&mut after, mv);
+        if !is_player_in_check(&after, opponent) {
+            moves.push(mv);
+        }
+    }
+
+    moves
+}
+
+fn race_result(game_state: &GameState) -> Option<GameResult> {
+    let reached = |player: Player| game_state.pieces_of(PieceType::King, player) & EIGHTH_RANK != 0;
+    let white_won = GameResult::Win { winner: Player::White, reason: WinReason::ReachedEighthRank };
+
+    match (reached(Player::White), reached(Player::Black)) {
+        (true, true) => Some(GameResult::Draw { reason: DrawReason::BothReachedEighthRank }),
+        (false, true) => Some(GameResult::Win { winner: Player::Black, reason: WinReason::ReachedEighthRank }),
+        (true, false) if game_state.player_to_move == Player::White => Some(white_won),
+        // Black still has the move to catch up with
+        (true, false) => {
+            let king = game_state.king_square(Player::Black);
+            let catches_up = racing_moves(game_state).iter()
+                .any(|mv| Some(mv.origin()) == king && EIGHTH_RANK & bit(mv.destination()) != 0);
+            if catches_up { None } else { Some(white_won) }
+        },
+        (false, false) => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RacingKings;
+
+impl Variant for RacingKings {
+    fn name(&self) -> &'static str {
+        "racingkings"
+    }
+
+    fn starting_fen(&self) -> &'static str {
+        "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1"
+    }
+
+    // neither king may be in check, and with no pawns every piece beyond
+    // the original set would have had to promote
+    fn validate(&self, game_state: &GameState) -> Result<(), Vec<PositionError>> {
+        let mut errors = position_errors_except(game_state, |err| {
+            matches!(err, PositionError::OpponentInCheck { .. } |
+                          PositionError::CastlingWithoutPieces { .. } |
+                          PositionError::TooManyPromotedPieces { .. })
+        });
+
+        if game_state.castling_rights != CastlingRights::none() {
+            errors.push(PositionError::CastlingNotAllowed);
+        }
+        if game_state.pieces[PieceType::Pawn as usize] != 0 {
+            errors.push(PositionError::PawnsNotAllowed);
+        }
+
+        for player in [Player::White, Player::Black] {
+            let count = |piece: PieceType| game_state.pieces_of(piece, player).count_ones();
+            if count(PieceType::Queen) > 1 || count(PieceType::Rook) > 2 || count(PieceType::Bishop) > 2 ||
+               count(PieceType::Knight) > 2 {
+                errors.push(PositionError::TooManyPromotedPieces { player });
+            }
+        }
+
+        if errors.iter().all(|err| !matches!(err, PositionError::WrongKingCount { .. })) {
+            for player in [Player::White, Player::Black] {
+                if is_player_in_check(game_state, player) {
+                    errors.push(PositionError::CheckNotAllowed { player });
+                }
+            }
+        }
+
+        into_result(errors)
+    }
+
+    fn legal_moves(&self, game_state: &GameState) -> MoveList {
+        match race_result(game_state) {
+            Some(_) => MoveList::new(),
+            None => racing_moves(game_state),
+        }
+    }
+
+    // no draw by insufficient material: a bare king can still win the race
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        if let Some(result) = race_result(game_state) {
+            return Some(result);
+        }
+
+        status_or_move_rule(self, game_state)
+    }
+}
@@ -0,0 +1,471 @@
+//! The original mailbox move generator. It walks rays square by square and
+//! is much slower than the bitboard one, but it is simple enough to trust,
+//! so it is kept around to test the fast generator against. It only knows
+//! standard castling, with the king on e1/e8 and rooks in the corners.
+
+use crate::bitboard::{row_col, square};
+use crate::game_state::{CastlingSide, GameState, PieceType, Player};
+use crate::movegen::{make_move, unmake_move};
+use crate::moves::Move;
+
+// a move to a square that is empty or holds an enemy piece
+fn new_move(game_state: &GameState, from: usize, to: usize) -> Move {
+    let (row, col) = row_col(to);
+    if game_state.owner_at(row, col) == Player::None {
+        Move::new(from, to)
+    } else {
+        Move::new_capture(from, to)
+    }
+}
+
+fn generate_rook_attacks(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    for o in y+1..8 {
+        let target_owner = game_state.owner_at(o, x);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(o,x)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+    for o in (0..y).rev() {
+        let target_owner = game_state.owner_at(o, x);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(o,x)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+    for o in x+1..8 {
+        let target_owner = game_state.owner_at(y, o);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(y,o)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+    for o in (0..x).rev() {
+        let target_owner = game_state.owner_at(y, o);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(y,o)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+
+    moves
+}
+
+fn generate_rook_moves(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    generate_rook_attacks(game_state, owner, x, y)
+}
+
+fn generate_bishop_attacks(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    for o in 1..8 {
+        if y+o > 7 || x+o > 7 {
+            break;
+        }
+        let target_owner = game_state.owner_at(y+o, x+o);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(y+o,x+o)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+    for o in 1..8 {
+        if y < o || x < o {
+            break;
+        }
+        let target_owner = game_state.owner_at(y-o, x-o);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(y-o, x-o)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+    for o in 1..8 {
+        if y+o > 7 || x < o {
+            break;
+        }
+        let target_owner = game_state.owner_at(y+o, x-o);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(y+o, x-o)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+    for o in 1..8 {
+        if y < o || x+o > 7 {
+            break;
+        }
+        let target_owner = game_state.owner_at(y-o, x+o);
+        if target_owner != owner {
+            moves.push(new_move(game_state, square(y,x), square(y-o, x+o)))
+        }
+        if target_owner != Player::None {
+            break;
+        }
+    }
+
+    moves
+}
+
+fn generate_bishop_moves(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    generate_bishop_attacks(game_state, owner, x, y)
+}
+
+fn generate_pawn_attacks(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    let mut sign = 1isize;
+    if owner == Player::White {
+        sign = -1;
+    }
+
+    // x-1
+    {
+        let o = y as isize + sign;
+        if (0..=7).contains(&o) && x >= 1 {
+            let mv = Move::new_capture(square(y,x), square(o as usize,x-1));
+
+            let target_owner = game_state.owner_at(o as usize, x-1);
+            if target_owner != Player::None && target_owner != owner {
+                if (owner == Player::White && o == 0) ||
+                   (owner == Player::Black && o == 7) {
+                    for pt in PieceType::iterator() {
+                        moves.push(Move::new_capture_promotion(mv.origin(), mv.destination(), pt));
+                    }
+                } else {
+                    moves.push(mv);
+                }
+            }
+        }
+    }
+
+    // x+1
+    {
+        let o = y as isize + sign;
+        if (0..=7).contains(&o) && x < 7 {
+            let mv = Move::new_capture(square(y,x), square(o as usize,x+1));
+
+            let target_owner = game_state.owner_at(o as usize, x+1);
+            if target_owner != Player::None && target_owner != owner {
+                if (owner == Player::White && o == 0) ||
+                   (owner == Player::Black && o == 7) {
+                    for pt in PieceType::iterator() {
+                        moves.push(Move::new_capture_promotion(mv.origin(), mv.destination(), pt));
+                    }
+                } else {
+                    moves.push(mv);
+                }
+            }
+        }
+    }
+
+    moves
+}
+
+fn generate_pawn_moves(game_state: &GameState, player: Player, x: usize, y: usize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    moves.extend(generate_pawn_attacks(game_state, player, x, y));
+
+    let mut sign = 1isize;
+    if player == Player::White {
+        sign = -1;
+    }
+
+    for i in 1..3isize {
+        let o = y as isize + sign * i;
+        if !(0..=7).contains(&o) {
+            break;
+        }
+        if i == 2 && ((player == Player::White && y != 6) ||
+                      (player == Player::Black && y != 1)) {
+            break;
+        }
+        let target_owner = game_state.owner_at(o as usize, x);
+        if target_owner == Player::None {
+            let mv = if i == 2 {
+                Move::new_double_push(square(y,x), square(o as usize, x))
+            } else {
+                Move::new(square(y,x), square(o as usize, x))
+            };
+            if i == 1 && ((player == Player::White && o == 0) ||
+                          (player == Player::Black && o == 7)) {
+                for pt in PieceType::iterator() {
+                    moves.push(Move::new_promotion(mv.origin(), mv.destination(), pt));
+                }
+                continue;
+            }
+            moves.push(mv);
+        } else {
+            break;
+        }
+    }
+
+    if let Some(target) = game_state.en_passant_target {
+        let t_y = target.0 as isize;
+        let t_x = target.1 as isize;
+
+        if t_y == y as isize && (t_x-1 == x as isize || t_x+1 == x as isize) {
+            let o = t_y + sign;
+            if (0..=7).contains(&o) {
+                moves.push(Move::new_en_passant(square(y, x), square(o as usize, t_x as usize)));
+            }
+        }
+    }
+
+    moves
+}
+
+fn generate_knight_attacks(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    let offsets = [(2, -1), (2, 1), (-2, -1), (-2, 1),
+                   (1, -2), (1, 2), (-1, -2), (-1, 2)];
+
+    for (dy, dx) in offsets {
+        let o = y as isize + dy;
+        let p = x as isize + dx;
+        if (0..=7).contains(&o) && (0..=7).contains(&p) {
+            let target_owner = game_state.owner_at(o as usize, p as usize);
+            if target_owner != owner {
+                moves.push(new_move(game_state, square(y,x), square(o as usize, p as usize)));
+            }
+        }
+    }
+
+    moves
+}
+
+fn generate_knight_moves(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    generate_knight_attacks(game_state, owner, x, y)
+}
+
+fn generate_king_attacks(game_state: &GameState, owner: Player, x: isize, y: isize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    for o in y-1..y+2 {
+        for p in x-1..x+2 {
+            if o == y && p == x {
+                continue;
+            }
+
+            if !(0..=7).contains(&o) {
+                continue;
+            }
+
+            if !(0..=7).contains(&p) {
+                continue;
+            }
+
+            let target_owner = game_state.owner_at(o as usize, p as usize);
+            if target_owner != owner {
+                moves.push(new_move(game_state, square(y as usize,x as usize), square(o as usize, p as usize)));
+            }
+        }
+    }
+
+    moves
+}
+
+// TODO: optimize this
+fn generate_king_moves(game_state: &GameState, owner: Player, x: isize, y: isize, is_in_check: bool) -> Vec<Move> {
+    let mut moves = vec![];
+
+    moves.extend(generate_king_attacks(game_state, owner, x, y));
+
+    let is_empty = |row: usize, col: usize| game_state.piece_at(row, col).0 == PieceType::None;
+    let mut scratch = *game_state;
+
+    if !is_in_check {
+        match owner {
+            Player::Black => {
+                if game_state.castling_rights.allowed(Player::Black, CastlingSide::Kingside) &&
+                   is_empty(0, 5) && is_empty(0, 6) &&
+                   is_move_valid(&mut scratch, Move::new(square(0, 4), square(0, 5))) {
+                    moves.push(Move::new_castling(square(0, 4), square(0, 7)));
+                }
+                if game_state.castling_rights.allowed(Player::Black, CastlingSide::Queenside) &&
+                   is_empty(0, 3) && is_empty(0, 2) && is_empty(0, 1) &&
+                   is_move_valid(&mut scratch, Move::new(square(0, 4), square(0, 3))) {
+                    moves.push(Move::new_castling(square(0, 4), square(0, 0)));
+                }
+            },
+            Player::White => {
+                if game_state.castling_rights.allowed(Player::White, CastlingSide::Kingside) &&
+                   is_empty(7, 5) && is_empty(7, 6) &&
+                   is_move_valid(&mut scratch, Move::new(square(7, 4), square(7, 5))) {
+                    moves.push(Move::new_castling(square(7, 4), square(7, 7)));
+                }
+                if game_state.castling_rights.allowed(Player::White, CastlingSide::Queenside) &&
+                   is_empty(7, 3) && is_empty(7, 2) && is_empty(7, 1) &&
+                   is_move_valid(&mut scratch, Move::new(square(7, 4), square(7, 3))) {
+                    moves.push(Move::new_castling(square(7, 4), square(7, 0)));
+                }
+            },
+            _ => { panic!("Invalid player at generate_king_moves!"); }
+        }
+    }
+
+    moves
+}
+
+fn generate_queen_attacks(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    let mut moves = vec![];
+
+    moves.extend(generate_rook_moves(game_state, owner, x, y));
+    moves.extend(generate_bishop_moves(game_state, owner, x, y));
+
+    moves
+}
+
+fn generate_queen_moves(game_state: &GameState, owner: Player, x: usize, y: usize) -> Vec<Move> {
+    generate_queen_attacks(game_state, owner, x, y)
+}
+
+fn is_in_check(game_state: &GameState, player: Player, moves: Vec<Move>) -> bool {
+    for mv in moves {
+        let (row, col) = mv.to();
+        let (target_piece, target_owner) = game_state.piece_at(row, col);
+
+        if target_owner == player && target_piece == PieceType::King {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn generate_attacks(game_state: &GameState, player: Player) -> Vec<Move> {
+    let mut attacks = vec![];
+
+    for y in 0..8 {
+        for x in 0..8 {
+            let (piece, owner) = game_state.piece_at(y, x);
+
+            if player == owner {
+                match piece {
+                    PieceType::Rook => {
+                        attacks.extend(generate_rook_attacks(game_state, owner, x, y));
+                    },
+                    PieceType::Knight => {
+                        attacks.extend(generate_knight_attacks(game_state, owner, x, y));
+                    },
+                    PieceType::Bishop => {
+                        attacks.extend(generate_bishop_attacks(game_state, owner, x, y));
+                    },
+                    PieceType::Queen => {
+                        attacks.extend(generate_queen_attacks(game_state, owner, x, y));
+                    },
+                    PieceType::King => {
+                        attacks.extend(generate_king_attacks(game_state, owner, x as isize, y as isize));
+                    },
+                    PieceType::Pawn => {
+                        attacks.extend(generate_pawn_attacks(game_state, owner, x, y));
+                    },
+                    PieceType::None => { continue; },
+                }
+            }
+        }
+    }
+
+    attacks
+}
+
+// plays the move in place and takes it back, leaving `game_state` unchanged
+fn is_move_valid(game_state: &mut GameState, mv: Move) -> bool {
+    let moving_player = game_state.player_to_move;
+
+    let undo = make_move(game_state, mv);
+    let attacks = generate_attacks(game_state, game_state.player_to_move);
+    let valid = !is_in_check(game_state, moving_player, attacks);
+    unmake_move(game_state, mv, undo);
+
+    valid
+}
+
+/// All legal moves for the side to move, found by walking the mailbox
+/// board square by square.
+pub fn generate_legal_moves(game_state: &GameState) -> Vec<Move> {
+    let mut moves = vec![];
+
+    let opp_attacks = if game_state.player_to_move == Player::Black {
+        generate_attacks(game_state, Player::White)
+    } else {
+        assert!(game_state.player_to_move == Player::White);
+        generate_attacks(game_state, Player::Black)
+    };
+
+    let player_in_check = is_in_check(game_state, game_state.player_to_move, opp_attacks);
+
+    for y in 0..8 {
+        for x in 0..8 {
+            let (piece, owner) = game_state.piece_at(y, x);
+
+            if game_state.player_to_move == owner {
+                match piece {
+                    PieceType::Rook => {
+                        moves.extend(generate_rook_moves(game_state, owner, x, y));
+                    },
+                    PieceType::Knight => {
+                        moves.extend(generate_knight_moves(game_state, owner, x, y));
+                    },
+                    PieceType::Bishop => {
+                        moves.extend(generate_bishop_moves(game_state, owner, x, y));
+                    },
+                    PieceType::Queen => {
+                        moves.extend(generate_queen_moves(game_state, owner, x, y));
+                    },
+                    PieceType::King => {
+                        moves.extend(generate_king_moves(game_state, owner, x as isize, y as isize, player_in_check));
+                    },
+                    PieceType::Pawn => {
+                        moves.extend(generate_pawn_moves(game_state, owner, x, y));
+                    },
+                    PieceType::None => { continue; },
+                }
+            }
+        }
+    }
+
+    let mut final_moves = vec![];
+    let mut scratch = *game_state;
+
+    // remove moves that would leave the player in check
+    for mv in &moves {
+        if is_move_valid(&mut scratch, *mv) {
+            final_moves.push(*mv);
+        }
+    }
+
+    final_moves
+}
+
+/// Counts the leaf nodes `n` plies deep using this generator.
+pub fn perft(game_state: &GameState, n: usize) -> usize {
+    if n == 0 {
+        return 1;
+    }
+
+    let mut scratch = *game_state;
+    let mut count = 0;
+    for mv in generate_legal_moves(game_state) {
+        let undo = make_move(&mut scratch, mv);
+        count += perft(&scratch, n - 1);
+        unmake_move(&mut scratch, mv, undo);
+    }
+
+    count
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use crate::game_state::{GameState, Player};
+use crate::material::{has_insufficient_material, is_insufficient_material};
+use crate::attacks::is_player_in_check;
+use crate::movegen::generate_legal_moves;
+
+/// How a game was won.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WinReason {
+    Checkmate,
+    /// The opponent ran out of time.
+    Timeout,
+    /// King of the Hill: the king reached d4, e4, d5 or e5.
+    KingOfTheHill,
+    /// Three-check: the opponent was checked for the third time.
+    ThirdCheck,
+    /// Atomic: the opponent's king was caught in an explosion.
+    KingExploded,
+    /// Antichess: the winner has no pieces left.
+    AllPiecesLost,
+    /// Antichess: the winner has no legal move.
+    Stalemated,
+    /// Horde: every piece of the horde was captured.
+    HordeCaptured,
+    /// Racing Kings: the king reached the eighth rank first.
+    ReachedEighthRank,
+}
+
+/// How a game was drawn.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DrawReason {
+    Stalemate,
+    /// Claimable by either side.
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    /// Claimable after 50 moves by each side without a capture or pawn move.
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    /// Neither side has enough material left to checkmate.
+    InsufficientMaterial,
+    /// Racing Kings: Black's king reached the eighth rank right after White's.
+    BothReachedEighthRank,
+}
+
+/// Final outcome of a game.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GameResult {
+    Win { winner: Player, reason: WinReason },
+    Draw { reason: DrawReason },
+}
+
+impl GameResult {
+    /// The side that won, or `None` for a draw.
+    pub fn winner(&self) -> Option<Player> {
+        match self {
+            GameResult::Win { winner, .. } => Some(*winner),
+            GameResult::Draw { .. } => None,
+        }
+    }
+}
+
+/// PGN result notation: "1-0", "0-1" or "1/2-1/2".
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.winner() {
+            Some(Player::White) => write!(f, "1-0"),
+            Some(_) => write!(f, "0-1"),
+            None => write!(f, "1/2-1/2"),
+        }
+    }
+}
+
+/// Whether the side to move can still play on in a position.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PositionStatus {
+    Ongoing,
+    Checkmate { winner: Player },
+    Stalemate,
+}
+
+impl PositionStatus {
+    /// The result this status ends the game with, if any.
+    pub fn result(&self) -> Option<GameResult> {
+        match self {
+            PositionStatus::Ongoing => None,
+            PositionStatus::Checkmate { winner } =>
+                Some(GameResult::Win { winner: *winner, reason: WinReason::Checkmate }),
+            PositionStatus::Stalemate =>
+                Some(GameResult::Draw { reason: DrawReason::Stalemate }),
+        }
+    }
+}
+
+/// Whether the side to move is in check.
+pub fn is_check(game_state: &GameState) -> bool {
+    is_player_in_check(game_state, game_state.player_to_move)
+}
+
+/// Classifies a position by whether the side to move has any legal move.
+pub fn position_status(game_state: &GameState) -> PositionStatus {
+    if !generate_legal_moves(game_state).is_empty() {
+        return PositionStatus::Ongoing;
+    }
+
+    if is_check(game_state) {
+        PositionStatus::Checkmate { winner: game_state.player_to_move.opponent() }
+    } else {
+        PositionStatus::Stalemate
+    }
+}
+
+/// Fifty moves by each side without a capture or pawn move; a draw may be claimed.
+pub fn is_fifty_move_draw(game_state: &GameState) -> bool {
+    game_state.halfmove_counter >= 100
+}
+
+/// Seventy-five moves by each side without a capture or pawn move; the game
+/// is drawn unless the last move delivered checkmate.
+pub fn is_seventy_five_move_draw(game_state: &GameState) -> bool {
+    game_state.halfmove_counter >= 150
+}
+
+/// The result if the position alone ends the game: checkmate, stalemate,
+/// insufficient material or the seventy-five-move rule. Checkmate takes
+/// precedence over the move rule.
+pub fn game_result(game_state: &GameState) -> Option<GameResult> {
+    if let Some(result) = position_status(game_state).result() {
+        return Some(result);
+    }
+
+    if is_insufficient_material(game_state) {
+        return Some(GameResult::Draw { reason: DrawReason::InsufficientMaterial });
+    }
+
+    seventy_five_move_result(game_state)
+}
+
+/// The draw by the seventy-five-move rule, which every variant ends games
+/// with once nothing else has.
+pub(crate) fn seventy_five_move_result(game_state: &GameState) -> Option<GameResult> {
+    if is_seventy_five_move_draw(game_state) {
+        Some(GameResult::Draw { reason: DrawReason::SeventyFiveMoveRule })
+    } else {
+        None
+    }
+}
+
+/// The result when `flagged` runs out of time: a loss, unless the opponent
+/// could never have checkmated, in which case it is a draw.
+pub fn timeout_result(game_state: &GameState, flagged: Player) -> GameResult {
+    let opponent = flagged.opponent();
+    if has_insufficient_material(game_state, opponent) {
+        GameResult::Draw { reason: DrawReason::InsufficientMaterial }
+    } else {
+        GameResult::Win { winner: opponent, reason: WinReason::Timeout }
+    }
+}
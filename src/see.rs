@@ -0,0 +1,115 @@
+use crate::attacks::attackers_to;
+use crate::bitboard::{bit, square, Bitboard};
+use crate::game_state::{GameState, PieceType};
+use crate::magic::{bishop_attacks, rook_attacks};
+use crate::moves::{Move, MoveKind};
+
+// cheapest first, the order attackers are tried in
+const EXCHANGE_ORDER: [PieceType; 6] =
+    [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+
+/// Material value of a piece in centipawns, as used by `see`.
+pub fn piece_value(piece: PieceType) -> i32 {
+    match piece {
+        PieceType::None => 0,
+        PieceType::Pawn => 100,
+        PieceType::Knight | PieceType::Bishop => 300,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        // never actually captured; only needs to outweigh everything else
+        PieceType::King => 20000,
+    }
+}
+
+/// Static exchange evaluation: the material the side to move wins (or
+/// loses, if negative) in centipawns by playing `mv` and then letting both
+/// sides keep recapturing on the destination square, each with its least
+/// valuable attacker and each free to stop when continuing would lose
+/// material. Sliders lined up behind other attackers join in as the pieces
+/// in front of them capture. Pins and checks are not considered.
+///
+/// `mv` should be a legal move in `game_state`; quiet moves and drops are
+/// evaluated as offering the piece on its destination.
+pub fn see(game_state: &GameState, mv: Move) -> i32 {
+    if mv.kind() == MoveKind::Castling {
+        return 0;
+    }
+
+    let (from, to) = (mv.from(), mv.to());
+    let target = square(to.0, to.1);
+    let (moving_piece, player) = match mv.drop_piece() {
+        Some(piece) => (piece, game_state.player_to_move),
+        None => game_state.piece_at(from.0, from.1),
+    };
+
+    // a drop's origin is its empty destination, which this marks occupied
+    let mut occupied = game_state.occupied() ^ bit(square(from.0, from.1));
+    let mut gains = [0i32; 32];
+    gains[0] = piece_value(game_state.piece_at(to.0, to.1).0);
+    // the piece that now stands on the target square, waiting to be taken
+    let mut on_target = moving_piece;
+
+    match mv.kind() {
+        MoveKind::EnPassant => {
+            occupied ^= bit(square(from.0, to.1));
+            gains[0] = piece_value(PieceType::Pawn);
+        },
+        MoveKind::Promotion | MoveKind::CapturePromotion => {
+            let promotion = mv.promotion().unwrap();
+            gains[0] += piece_value(promotion) - piece_value(PieceType::Pawn);
+            on_target = promotion;
+        },
+        _ => {},
+    }
+
+    let queens = game_state.pieces[PieceType::Queen as usize];
+    let diagonal_sliders = game_state.pieces[PieceType::Bishop as usize] | queens;
+    let straight_sliders = game_state.pieces[PieceType::Rook as usize] | queens;
+
+    let mut attackers = attackers_to(game_state, target, occupied) & occupied;
+    let mut side = player.opponent();
+    let mut depth = 0;
+
+    loop {
+        let own_attackers = attackers & game_state.colors[side as usize];
+        let (piece, attacker) = match least_valuable(game_state, own_attackers) {
+            Some(found) => found,
+            None => break,
+        };
+
+        // the king can only recapture if nothing would take it back
+        if piece == PieceType::King && attackers & game_state.colors[side.opponent() as usize] != 0 {
+            break;
+        }
+
+        depth += 1;
+        gains[depth] = piece_value(on_target) - gains[depth - 1];
+        on_target = piece;
+
+        occupied ^= attacker;
+        attackers &= occupied;
+        // uncover sliders standing behind the piece that just captured
+        attackers |= (bishop_attacks(target, occupied) & diagonal_sliders |
+                      rook_attacks(target, occupied) & straight_sliders) & occupied;
+        side = side.opponent();
+    }
+
+    // each side picks the better of capturing or standing pat, from the end back
+    while depth > 0 {
+        gains[depth - 1] = -i32::max(-gains[depth - 1], gains[depth]);
+        depth -= 1;
+    }
+
+    gains[0]
+}
+
+fn least_valuable(game_state: &GameState, attackers: Bitboard) -> Option<(PieceType, Bitboard)> {
+    EXCHANGE_ORDER.iter().find_map(|piece| {
+        let candidates = attackers & game_state.pieces[*piece as usize];
+        if candidates == 0 {
+            None
+        } else {
+            Some((*piece, candidates & candidates.wrapping_neg()))
+        }
+    })
+}
@@ -0,0 +1,78 @@
+//! Three-check: standard chess, except that checking the opponent for the
+//! third time also wins. The checks given so far are part of the position
+//! and appear in FEN as a seventh field, `+N+M` for White and Black.
+
+use crate::fen::{parse_three_check_fen, FenError};
+use crate::game_state::{GameState, PieceType, Player};
+use crate::movegen::{generate_legal_moves, make_move};
+use crate::moves::{Move, MoveList};
+use crate::result::{is_check, seventy_five_move_result, DrawReason, GameResult, WinReason};
+use crate::variant::Variant;
+use crate::zobrist::checks_key;
+
+const CHECKS_TO_WIN: u8 = 3;
+
+fn third_check_by(game_state: &GameState) -> Option<Player> {
+    [Player::White, Player::Black].into_iter()
+        .find(|player| game_state.checks_given(*player).is_some_and(|checks| checks >= CHECKS_TO_WIN))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ThreeCheck;
+
+impl Variant for ThreeCheck {
+    fn name(&self) -> &'static str {
+        "3check"
+    }
+
+    fn starting_fen(&self) -> &'static str {
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +0+0"
+    }
+
+    // a record without check counts starts both sides at zero
+    fn position_from_fen(&self, fen: &str) -> Result<GameState, FenError> {
+        let mut game_state = parse_three_check_fen(fen)?;
+        game_state.checks_given.get_or_insert([0; 3]);
+        self.validate(&game_state).map_err(FenError::InvalidPosition)?;
+
+        Ok(game_state)
+    }
+
+    fn legal_moves(&self, game_state: &GameState) -> MoveList {
+        match third_check_by(game_state) {
+            Some(_) => MoveList::new(),
+            None => generate_legal_moves(game_state),
+        }
+    }
+
+    fn play(&self, game_state: &mut GameState, mv: Move) {
+        make_move(game_state, mv);
+        if !is_check(game_state) {
+            return;
+        }
+
+        let mover = game_state.player_to_move.opponent();
+        if let Some(checks) = &mut game_state.checks_given {
+            let given = &mut checks[mover as usize];
+            game_state.zobrist_key ^= checks_key(mover, *given) ^ checks_key(mover, *given + 1);
+            *given += 1;
+        }
+    }
+
+    // only bare kings are insufficient material, since any other piece can give check
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        if let Some(winner) = third_check_by(game_state) {
+            return Some(GameResult::Win { winner, reason: WinReason::ThirdCheck });
+        }
+
+        if let Some(result) = self.position_status(game_state).result() {
+            return Some(result);
+        }
+
+        if game_state.occupied() == game_state.pieces[PieceType::King as usize] {
+            return Some(GameResult::Draw { reason: DrawReason::InsufficientMaterial });
+        }
+
+        seventy_five_move_result(game_state)
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+
+use crate::attacks::is_player_in_check;
+use crate::game_state::{square_name, CastlingSide, GameState, PieceType, Player};
+
+/// A reason a position can not arise in a legal game.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PositionError {
+    /// The given side does not have exactly one king.
+    WrongKingCount { player: Player, count: usize },
+    /// A pawn on the first or eighth rank, as (line, column).
+    PawnOnBackRank { square: (usize, usize) },
+    /// More than eight pawns for one side.
+    TooManyPawns { player: Player, count: usize },
+    /// More than sixteen pieces for one side.
+    TooManyPieces { player: Player, count: usize },
+    /// More extra queens, rooks, bishops and knights than missing pawns could explain.
+    TooManyPromotedPieces { player: Player },
+    /// The side that just moved has left its king in check.
+    OpponentInCheck { player: Player },
+    /// A castling right without the king or rook on its original square.
+    CastlingWithoutPieces { player: Player, side: CastlingSide },
+    /// Pockets or pieces marked as promoted in a variant without pockets.
+    PocketsNotAllowed,
+    /// A castling right in a variant without castling.
+    CastlingNotAllowed,
+    /// Pawns in a variant without pawns.
+    PawnsNotAllowed,
+    /// A king in check in a variant where checks can't be given.
+    CheckNotAllowed { player: Player },
+    /// An en passant target without a double-pushed pawn in front of it,
+    /// or with the squares it passed through occupied.
+    InvalidEnPassant { square: (usize, usize) },
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PositionError::WrongKingCount { player, count } =>
+                write!(f, "{:?} has {} kings instead of 1", player, count),
+            PositionError::PawnOnBackRank { square } =>
+                write!(f, "pawn on back rank at {}", square_name(square.0, square.1)),
+            PositionError::TooManyPawns { player, count } =>
+                write!(f, "{:?} has {} pawns", player, count),
+            PositionError::TooManyPieces { player, count } =>
+                write!(f, "{:?} has {} pieces", player, count),
+            PositionError::TooManyPromotedPieces { player } =>
+                write!(f, "{:?} has more promoted pieces than missing pawns", player),
+            PositionError::OpponentInCheck { player } =>
+                write!(f, "{:?} is in check but it is not their move", player),
+            PositionError::CastlingWithoutPieces { player, side } =>
+                write!(f, "{:?} {:?} castling right without king and rook in place", player, side),
+            PositionError::PocketsNotAllowed =>
+                write!(f, "pockets in a variant without drops"),
+            PositionError::CastlingNotAllowed =>
+                write!(f, "castling rights in a variant without castling"),
+            PositionError::PawnsNotAllowed =>
+                write!(f, "pawns in a variant without pawns"),
+            PositionError::CheckNotAllowed { player } =>
+                write!(f, "{:?} is in check in a variant without checks", player),
+            PositionError::InvalidEnPassant { square } =>
+                write!(f, "no double-pushed pawn at {} for en passant", square_name(square.0, square.1)),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// Checks that `game_state` could occur in a legal game, reporting every
+/// problem found. Positions accepted here are safe to generate moves for.
+pub fn validate_position(game_state: &GameState) -> Result<(), Vec<PositionError>> {
+    let mut errors = vec![];
+
+    for player in [Player::White, Player::Black] {
+        let mut counts = [0usize; 7];
+        for row in 0..8 {
+            for col in 0..8 {
+                let (piece, owner) = game_state.piece_at(row, col);
+                if owner == player {
+                    counts[piece as usize] += 1;
+                }
+            }
+        }
+        let count_of = |piece: PieceType| counts[piece as usize];
+
+        if count_of(PieceType::King) != 1 {
+            errors.push(PositionError::WrongKingCount { player, count: count_of(PieceType::King) });
+        }
+        if count_of(PieceType::Pawn) > 8 {
+            errors.push(PositionError::TooManyPawns { player, count: count_of(PieceType::Pawn) });
+        }
+        let total: usize = counts.iter().sum();
+        if total > 16 {
+            errors.push(PositionError::TooManyPieces { player, count: total });
+        }
+        let promoted = count_of(PieceType::Queen).saturating_sub(1) +
+                       count_of(PieceType::Rook).saturating_sub(2) +
+                       count_of(PieceType::Bishop).saturating_sub(2) +
+                       count_of(PieceType::Knight).saturating_sub(2);
+        if promoted > 0 && count_of(PieceType::Pawn) + promoted > 8 {
+            errors.push(PositionError::TooManyPromotedPieces { player });
+        }
+    }
+
+    for row in [0, 7] {
+        for col in 0..8 {
+            if game_state.piece_at(row, col).0 == PieceType::Pawn {
+                errors.push(PositionError::PawnOnBackRank { square: (row, col) });
+            }
+        }
+    }
+
+    // only meaningful once both sides have exactly one king
+    if errors.iter().all(|err| !matches!(err, PositionError::WrongKingCount { .. })) {
+        let waiting = game_state.player_to_move.opponent();
+        if is_player_in_check(game_state, waiting) {
+            errors.push(PositionError::OpponentInCheck { player: waiting });
+        }
+    }
+
+    // the king may stand on any file of its first rank, as in Chess960, as
+    // long as the castling rook is on the side the right is for
+    let rights = game_state.castling_rights;
+    for player in [Player::White, Player::Black] {
+        for side in [CastlingSide::Kingside, CastlingSide::Queenside] {
+            let rook_col = match rights.rook_file(player, side) {
+                Some(file) => file,
+                None => continue,
+            };
+            let row = player.home_row();
+            let king_col = (0..8).find(|col| game_state.piece_at(row, *col) == (PieceType::King, player));
+            let rook_beside_king = match (king_col, side) {
+                (Some(king_col), CastlingSide::Kingside) => rook_col > king_col,
+                (Some(king_col), CastlingSide::Queenside) => rook_col < king_col,
+                (None, _) => false,
+            };
+            if !rook_beside_king || game_state.piece_at(row, rook_col) != (PieceType::Rook, player) {
+                errors.push(PositionError::CastlingWithoutPieces { player, side });
+            }
+        }
+    }
+
+    if game_state.pockets.is_some() || game_state.promoted != 0 {
+        errors.push(PositionError::PocketsNotAllowed);
+    }
+
+    if let Some((pawn_row, col)) = game_state.en_passant_target {
+        let (pawn_row, col) = (pawn_row as usize, col as usize);
+        let pushed = game_state.player_to_move.opponent();
+        // the pawn came from two rows behind, passing through the target square
+        let (target_row, start_row) = if pushed == Player::White {
+            (pawn_row + 1, pawn_row + 2)
+        } else {
+            (pawn_row - 1, pawn_row - 2)
+        };
+        if game_state.piece_at(pawn_row, col) != (PieceType::Pawn, pushed) ||
+           game_state.piece_at(target_row, col).0 != PieceType::None ||
+           game_state.piece_at(start_row, col).0 != PieceType::None {
+            errors.push(PositionError::InvalidEnPassant { square: (target_row, col) });
+        }
+    }
+
+    into_result(errors)
+}
+
+/// What `validate_position` reports, less the errors `allowed` by a variant
+/// whose rules lift some of its limits.
+pub(crate) fn position_errors_except(game_state: &GameState, allowed: impl Fn(&PositionError) -> bool)
+    -> Vec<PositionError> {
+    validate_position(game_state).err().unwrap_or_default().into_iter().filter(|err| !allowed(err)).collect()
+}
+
+pub(crate) fn into_result(errors: Vec<PositionError>) -> Result<(), Vec<PositionError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
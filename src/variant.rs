@@ -0,0 +1,95 @@
+//! Pluggable rules. A `Variant` decides which moves are legal, what playing
+//! one does to the position and when the game is over; every method
+//! defaults to standard chess, so a variant only overrides what it changes.
+
+use crate::fen::{parse_fen, FenError, STARTING_FEN};
+use crate::game_state::GameState;
+use crate::movegen::{generate_legal_moves, make_move};
+use crate::moves::{Move, MoveList};
+use crate::result::{game_result, is_check, seventy_five_move_result, GameResult, PositionStatus};
+use crate::validation::{validate_position, PositionError};
+
+/// A set of chess rules. Variants are stateless; anything they need to
+/// remember between moves lives in the `GameState`.
+pub trait Variant {
+    /// Short lowercase name, e.g. "standard".
+    fn name(&self) -> &'static str;
+
+    /// FEN of the position games of this variant start from.
+    fn starting_fen(&self) -> &'static str {
+        STARTING_FEN
+    }
+
+    /// Checks that `game_state` could occur in a game of this variant.
+    fn validate(&self, game_state: &GameState) -> Result<(), Vec<PositionError>> {
+        validate_position(game_state)
+    }
+
+    /// Parses a FEN record and validates it with `validate`.
+    fn position_from_fen(&self, fen: &str) -> Result<GameState, FenError> {
+        let game_state = parse_fen(fen)?;
+        self.validate(&game_state).map_err(FenError::InvalidPosition)?;
+
+        Ok(game_state)
+    }
+
+    /// Every legal move for the side to move. Empty after checkmate or
+    /// stalemate and once a variant's own goal is reached, like the hill or
+    /// a third check, but not after a draw by material or the move rules.
+    fn legal_moves(&self, game_state: &GameState) -> MoveList {
+        generate_legal_moves(game_state)
+    }
+
+    /// The legal move written as `text` in the notation moves print with:
+    /// origin and destination plus any promotion piece, like `e7e8q`, or the
+    /// piece and destination of a drop, like `N@f3`. Castling is the king's
+    /// move, like `e1g1`, unless king and rook start off their standard
+    /// squares, where it is the king taking its rook, like `f1h1`.
+    fn parse_move(&self, game_state: &GameState, text: &str) -> Option<Move> {
+        self.legal_moves(game_state).into_iter().find(|mv| format!("{:?}", mv) == text)
+    }
+
+    /// Plays `mv`, which must be one of `legal_moves`.
+    fn play(&self, game_state: &mut GameState, mv: Move) {
+        make_move(game_state, mv);
+    }
+
+    /// Whether the side to move is in check, for variants that have checks.
+    fn is_check(&self, game_state: &GameState) -> bool {
+        is_check(game_state)
+    }
+
+    /// Whether the side to move is checkmated, stalemated or can play on,
+    /// going by `legal_moves` and `is_check`.
+    fn position_status(&self, game_state: &GameState) -> PositionStatus {
+        if !self.legal_moves(game_state).is_empty() {
+            PositionStatus::Ongoing
+        } else if self.is_check(game_state) {
+            PositionStatus::Checkmate { winner: game_state.player_to_move.opponent() }
+        } else {
+            PositionStatus::Stalemate
+        }
+    }
+
+    /// The result if the position alone ends the game.
+    fn game_result(&self, game_state: &GameState) -> Option<GameResult> {
+        game_result(game_state)
+    }
+}
+
+/// Checkmate or stalemate going by the variant's own moves, or else the
+/// seventy-five-move rule: how a game ends in variants where no material is
+/// ever insufficient.
+pub(crate) fn status_or_move_rule<V: Variant + ?Sized>(variant: &V, game_state: &GameState) -> Option<GameResult> {
+    variant.position_status(game_state).result().or_else(|| seventy_five_move_result(game_state))
+}
+
+/// Standard chess, Chess960 included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Standard;
+
+impl Variant for Standard {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+}
@@ -0,0 +1,183 @@
+use crate::bitboard::Squares;
+use crate::game_state::{CastlingRights, CastlingSide, GameState, PieceType, Player};
+
+/// Random 64-bit keys XORed together to form a position's Zobrist key.
+struct ZobristKeys {
+    // indexed by [Player][PieceType][line * 8 + column]; Player::None and
+    // PieceType::None stay zero so empty squares contribute nothing
+    pieces: [[[u64; 64]; 7]; 3],
+    castling: [u64; 16],
+    en_passant: [u64; 8],
+    black_to_move: u64,
+    // indexed by [Player][checks given], for Three-check
+    checks: [[u64; 4]; 3],
+    // indexed by [Player][PieceType][count in hand], for Crazyhouse
+    pockets: [[[u64; MAX_POCKET + 1]; 7]; 3],
+    promoted: [u64; 64],
+}
+
+// no pocket can hold more than the sixteen pawns of both sides
+const MAX_POCKET: usize = 16;
+
+// splitmix64, so the keys are fixed across builds and platforms
+const fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+impl ZobristKeys {
+    const fn generate() -> ZobristKeys {
+        let mut keys = ZobristKeys {
+            pieces: [[[0; 64]; 7]; 3],
+            castling: [0; 16],
+            en_passant: [0; 8],
+            black_to_move: 0,
+            checks: [[0; 4]; 3],
+            pockets: [[[0; MAX_POCKET + 1]; 7]; 3],
+            promoted: [0; 64],
+        };
+        let mut state = 0x78616472656973; // "xadreis"
+
+        let mut player = 1;
+        while player < 3 {
+            let mut piece = 1;
+            while piece < 7 {
+                let mut square = 0;
+                while square < 64 {
+                    keys.pieces[player][piece][square] = next_random(&mut state);
+                    square += 1;
+                }
+                piece += 1;
+            }
+            player += 1;
+        }
+
+        // no rights at all hashes to zero, like an empty square
+        let mut rights = 1;
+        while rights < 16 {
+            keys.castling[rights] = next_random(&mut state);
+            rights += 1;
+        }
+
+        let mut file = 0;
+        while file < 8 {
+            keys.en_passant[file] = next_random(&mut state);
+            file += 1;
+        }
+
+        keys.black_to_move = next_random(&mut state);
+
+        // no checks given hashes to zero, so standard positions are unaffected
+        let mut player = 1;
+        while player < 3 {
+            let mut count = 1;
+            while count < 4 {
+                keys.checks[player][count] = next_random(&mut state);
+                count += 1;
+            }
+            player += 1;
+        }
+
+        // empty pockets hash to zero as well
+        let mut player = 1;
+        while player < 3 {
+            let mut piece = 1;
+            while piece < 7 {
+                let mut count = 1;
+                while count <= MAX_POCKET {
+                    keys.pockets[player][piece][count] = next_random(&mut state);
+                    count += 1;
+                }
+                piece += 1;
+            }
+            player += 1;
+        }
+
+        let mut square = 0;
+        while square < 64 {
+            keys.promoted[square] = next_random(&mut state);
+            square += 1;
+        }
+
+        keys
+    }
+}
+
+static KEYS: ZobristKeys = ZobristKeys::generate();
+
+pub(crate) fn piece_key(piece: PieceType, owner: Player, row: usize, col: usize) -> u64 {
+    KEYS.pieces[owner as usize][piece as usize][row * 8 + col]
+}
+
+pub(crate) fn castling_key(rights: CastlingRights) -> u64 {
+    // only which rights remain is hashed; the rook files never change in a game
+    let index = (rights.allowed(Player::White, CastlingSide::Kingside) as usize) << 3 |
+                (rights.allowed(Player::White, CastlingSide::Queenside) as usize) << 2 |
+                (rights.allowed(Player::Black, CastlingSide::Kingside) as usize) << 1 |
+                rights.allowed(Player::Black, CastlingSide::Queenside) as usize;
+    KEYS.castling[index]
+}
+
+pub(crate) fn en_passant_key(target: Option<(u8, u8)>) -> u64 {
+    match target {
+        Some((_, file)) => KEYS.en_passant[file as usize],
+        None => 0,
+    }
+}
+
+pub(crate) fn checks_key(player: Player, checks: u8) -> u64 {
+    KEYS.checks[player as usize][checks as usize]
+}
+
+pub(crate) fn pocket_key(player: Player, piece: PieceType, count: u8) -> u64 {
+    KEYS.pockets[player as usize][piece as usize][usize::min(count as usize, MAX_POCKET)]
+}
+
+pub(crate) fn promoted_key(square: usize) -> u64 {
+    KEYS.promoted[square]
+}
+
+/// Toggled every time the side to move changes.
+pub(crate) fn side_to_move_key() -> u64 {
+    KEYS.black_to_move
+}
+
+/// Computes the Zobrist key of `game_state` from scratch. `make_move` and
+/// `unmake_move` keep `GameState::zobrist_key` equal to this incrementally.
+pub fn compute_zobrist_key(game_state: &GameState) -> u64 {
+    let mut key = 0;
+
+    for row in 0..8 {
+        for col in 0..8 {
+            let (piece, owner) = game_state.piece_at(row, col);
+            key ^= piece_key(piece, owner, row, col);
+        }
+    }
+
+    key ^= castling_key(game_state.castling_rights);
+    key ^= en_passant_key(game_state.en_passant_target);
+    if game_state.player_to_move == Player::Black {
+        key ^= side_to_move_key();
+    }
+
+    if let Some(checks) = game_state.checks_given {
+        key ^= checks_key(Player::White, checks[Player::White as usize]);
+        key ^= checks_key(Player::Black, checks[Player::Black as usize]);
+    }
+
+    if let Some(pockets) = game_state.pockets {
+        for player in [Player::White, Player::Black] {
+            for piece in PieceType::iterator().chain([PieceType::Pawn]) {
+                key ^= pocket_key(player, piece, pockets[player as usize][piece as usize]);
+            }
+        }
+    }
+    for square in Squares(game_state.promoted) {
+        key ^= promoted_key(square);
+    }
+
+    key
+}
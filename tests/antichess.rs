@@ -0,0 +1,75 @@
+use xadreis::{variant_perft, Antichess, DrawReason, Game, GameResult, Player, Variant, WinReason};
+
+fn nodes(fen: &str, depth: usize) -> usize {
+    variant_perft(&Antichess, &Antichess.position_from_fen(fen).unwrap(), depth)
+}
+
+#[test]
+fn antichess_perft() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1";
+    assert_eq!(nodes(start, 1), 20);
+    assert_eq!(nodes(start, 2), 400);
+    assert_eq!(nodes(start, 3), 8067);
+
+    // the pawns meet on the b-file, while on the c-file they pass by
+    let meeting = "8/1p6/8/8/8/8/P7/8 w - - 0 1";
+    assert_eq!(nodes(meeting, 1), 2);
+    assert_eq!(nodes(meeting, 2), 4);
+    assert_eq!(nodes(meeting, 3), 4);
+    assert_eq!(nodes(meeting, 4), 3);
+    assert_eq!(nodes(meeting, 5), 1);
+    assert_eq!(nodes(meeting, 6), 0);
+    let passing = "8/2p5/8/8/8/8/P7/8 w - - 0 1";
+    assert_eq!(nodes(passing, 1), 2);
+    assert_eq!(nodes(passing, 2), 4);
+    assert_eq!(nodes(passing, 3), 4);
+    assert_eq!(nodes(passing, 4), 4);
+    assert_eq!(nodes(passing, 5), 4);
+    assert_eq!(nodes(passing, 6), 4);
+    assert_eq!(nodes(passing, 7), 4);
+    assert_eq!(nodes(passing, 8), 4);
+    assert_eq!(nodes(passing, 9), 12);
+    assert_eq!(nodes(passing, 10), 36);
+}
+
+#[test]
+fn captures_are_compulsory() {
+    let game_state = Antichess.position_from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+    let moves = Antichess.legal_moves(&game_state);
+    assert_eq!(moves.len(), 1);
+    assert_eq!(format!("{:?}", moves[0]), "e4d5");
+
+    // kings are ordinary pieces: they can be captured and stand in "check"
+    let king = Antichess.position_from_fen("8/8/8/8/8/8/3k4/3R4 b - - 0 1").unwrap();
+    assert!(!Antichess.is_check(&king));
+    assert_eq!(Antichess.legal_moves(&king).len(), 1);
+
+    let promotion = Antichess.position_from_fen("8/P7/8/8/8/8/8/7k w - - 0 1").unwrap();
+    assert!(Antichess.parse_move(&promotion, "a7a8k").is_some());
+    assert_eq!(Antichess.legal_moves(&promotion).len(), 5);
+}
+
+#[test]
+fn positions() {
+    // no kings, or several, are fine; castling rights are not
+    assert!(Antichess.position_from_fen("8/8/8/8/8/8/8/KK5r w - - 0 1").is_ok());
+    assert!(Antichess.position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_err());
+}
+
+#[test]
+fn antichess_results() {
+    // black has to take white's last piece, so white wins
+    let game_state = Antichess.position_from_fen("1r6/7p/8/8/8/8/8/1R6 b - - 0 1").unwrap();
+    let mut game = Game::with_variant(game_state, &Antichess);
+    assert_eq!(game.result(), None);
+    game.play(Antichess.parse_move(game.current(), "b8b1").unwrap());
+    assert_eq!(game.result(), Some(GameResult::Win { winner: Player::White, reason: WinReason::AllPiecesLost }));
+
+    // a blocked pawn has no move, which wins for its side
+    let blocked = Antichess.position_from_fen("8/8/8/8/8/p7/P7/8 w - - 0 1").unwrap();
+    let stalemated = GameResult::Win { winner: Player::White, reason: WinReason::Stalemated };
+    assert_eq!(Antichess.game_result(&blocked), Some(stalemated));
+
+    let bishops = Antichess.position_from_fen("8/8/8/8/8/8/8/B6b w - - 0 1").unwrap();
+    assert_eq!(Antichess.game_result(&bishops), Some(GameResult::Draw { reason: DrawReason::InsufficientMaterial }));
+}
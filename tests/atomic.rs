@@ -0,0 +1,62 @@
+use xadreis::{game_state_to_fen, variant_perft, Atomic, Game, GameResult, Player, Variant, WinReason};
+
+fn nodes(fen: &str, depth: usize) -> usize {
+    variant_perft(&Atomic, &Atomic.position_from_fen(fen).unwrap(), depth)
+}
+
+#[test]
+fn atomic_perft() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_eq!(nodes(start, 1), 20);
+    assert_eq!(nodes(start, 2), 400);
+    assert_eq!(nodes(start, 3), 8902);
+    let middlegame = "rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1";
+    assert_eq!(nodes(middlegame, 1), 40);
+    assert_eq!(nodes(middlegame, 2), 1238);
+    assert_eq!(nodes(middlegame, 3), 45237);
+
+    // Chess960 castling next to the enemy king and with a rook behind the castling rook
+    let white = "8/8/8/8/8/8/2k5/rR4KR w KQ - 0 1";
+    assert_eq!(nodes(white, 1), 18);
+    assert_eq!(nodes(white, 2), 180);
+    let black = "r3k1rR/5K2/8/8/8/8/8/8 b kq - 0 1";
+    assert_eq!(nodes(black, 1), 25);
+    assert_eq!(nodes(black, 2), 282);
+    assert_eq!(nodes(black, 3), 6753);
+}
+
+#[test]
+fn kings_touching_and_capturing() {
+    // a king next to the other one is safe from the rook
+    let touching = Atomic.position_from_fen("8/8/8/8/8/8/3k4/3K3r w - - 0 1").unwrap();
+    assert!(!Atomic.is_check(&touching));
+    assert!(Atomic.parse_move(&touching, "d1e1").is_some());
+    // but it may not capture, even an undefended piece
+    assert!(Atomic.parse_move(&touching, "d1d2").is_none());
+    let pawn = Atomic.position_from_fen("8/8/8/8/8/8/4p3/4K2k w - - 0 1").unwrap();
+    assert!(Atomic.parse_move(&pawn, "e1e2").is_none());
+}
+
+#[test]
+fn explosions() {
+    // the rook takes the queen, and the blast takes the king next to it
+    let game_state = Atomic.position_from_fen("3qk3/8/8/8/8/8/8/3RK3 w - - 0 1").unwrap();
+    let mut game = Game::with_variant(game_state, &Atomic);
+    game.play(Atomic.parse_move(game.current(), "d1d8").unwrap());
+    assert_eq!(game.current().piece_at(0, 3).1, Player::None);
+    assert_eq!(game.current().piece_at(0, 4).1, Player::None);
+    assert!(game.legal_moves().is_empty());
+    assert_eq!(game.result(), Some(GameResult::Win { winner: Player::White, reason: WinReason::KingExploded }));
+
+    // pawns next to the capture survive; the capturing piece does not
+    let mut game_state = Atomic.position_from_fen("4k3/8/8/2ppn3/3P4/8/8/4K2B w - - 0 1").unwrap();
+    for name in ["h1e4", "d5e4"] {
+        let mv = Atomic.parse_move(&game_state, name).unwrap();
+        Atomic.play(&mut game_state, mv);
+    }
+    assert_eq!(game_state_to_fen(&game_state), "4k3/8/8/2p5/3P4/8/8/4K3 w - - 0 2");
+
+    // a capture that would blow up the mover's own king is illegal
+    let own_king = Atomic.position_from_fen("4k3/8/8/8/8/8/3p4/3RK3 w - - 0 1").unwrap();
+    assert!(Atomic.parse_move(&own_king, "d1d2").is_none());
+}
@@ -0,0 +1,95 @@
+use xadreis::{attackers, checkers, fen_to_game_state, is_attacked, pinned, xray_attackers, GameState, PieceType, Player};
+
+fn squares(set: xadreis::SquareSet) -> Vec<(usize, usize)> {
+    set.collect()
+}
+
+#[test]
+fn attackers_of_a_square() {
+    // e4 is hit by the knight on f6 and the pawn on d5, and defended by the bishop on c2
+    let game_state = fen_to_game_state("4k3/8/5n2/3p4/4P3/8/2B5/4K3 w - - 0 1").unwrap();
+    assert_eq!(squares(attackers(&game_state, (4, 4), Player::Black)), vec![(2, 5), (3, 3)]);
+    assert_eq!(squares(attackers(&game_state, (3, 3), Player::White)), vec![(4, 4)]);
+    assert!(is_attacked(&game_state, (4, 4), Player::Black));
+    assert!(is_attacked(&game_state, (4, 4), Player::White));
+    assert!(!is_attacked(&game_state, (5, 5), Player::Black));
+}
+
+#[test]
+fn checkers_and_pins() {
+    // the rook on e8 checks through the open file; the bishop on d2 is pinned by the queen on a5
+    let game_state = fen_to_game_state("4r1k1/8/8/q7/8/8/3B4/4K3 w - - 0 1").unwrap();
+    assert_eq!(squares(checkers(&game_state)), vec![(0, 4)]);
+    assert_eq!(squares(pinned(&game_state, Player::White)), vec![(6, 3)]);
+    assert!(pinned(&game_state, Player::Black).is_empty());
+}
+
+#[test]
+fn xray_attackers_behind_blockers() {
+    // doubled rooks on the d-file: only the front one attacks d8 directly
+    let game_state = fen_to_game_state("3k4/8/8/8/3R4/8/3R4/4K3 b - - 0 1").unwrap();
+    assert_eq!(squares(attackers(&game_state, (0, 3), Player::White)), vec![(4, 3)]);
+    assert_eq!(squares(xray_attackers(&game_state, (0, 3), Player::White)), vec![(6, 3)]);
+}
+
+// splitmix64, so the occupancies are the same on every run
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+// squares a slider on (row, col) attacks, walking each ray to the first blocker
+fn ray_walk(row: usize, col: usize, blockers: u64, directions: &[(isize, isize)]) -> Vec<(usize, usize)> {
+    let mut attacked = vec![];
+    for (dy, dx) in directions {
+        let (mut o, mut p) = (row as isize + dy, col as isize + dx);
+        while (0..8).contains(&o) && (0..8).contains(&p) {
+            attacked.push((o as usize, p as usize));
+            if blockers & 1 << (o * 8 + p) != 0 {
+                break;
+            }
+            o += dy;
+            p += dx;
+        }
+    }
+    attacked.sort();
+    attacked
+}
+
+// the lookup tables, magic or pext, against walking the rays, for a slider
+// on every square among random blockers
+#[test]
+fn slider_attacks_match_ray_walks() {
+    let sliders = [
+        (PieceType::Rook, [(1, 0), (-1, 0), (0, 1), (0, -1)]),
+        (PieceType::Bishop, [(1, 1), (1, -1), (-1, 1), (-1, -1)]),
+    ];
+    let mut state = 0;
+    for (piece, directions) in sliders {
+        for from in 0..64 {
+            let (row, col) = (from / 8, from % 8);
+            for round in 0..32 {
+                // sparse and dense boards alike
+                let mut blockers = next_random(&mut state);
+                for _ in 0..round % 3 {
+                    blockers &= next_random(&mut state);
+                }
+                blockers &= !(1 << from);
+
+                let mut game_state = GameState::empty();
+                game_state.set_piece_at(row, col, piece, Player::White);
+                for sq in (0..64).filter(|sq| blockers & 1 << sq != 0) {
+                    game_state.set_piece_at(sq / 8, sq % 8, PieceType::Pawn, Player::Black);
+                }
+
+                let attacked: Vec<_> = (0..64).map(|sq| (sq / 8, sq % 8))
+                    .filter(|target| attackers(&game_state, *target, Player::White).any(|sq| sq == (row, col)))
+                    .collect();
+                assert_eq!(attacked, ray_walk(row, col, blockers, &directions), "{:?} on {:?}", piece, (row, col));
+            }
+        }
+    }
+}
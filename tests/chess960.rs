@@ -0,0 +1,66 @@
+use xadreis::{chess960_position, double_chess960_position, fen_to_game_state, game_state_to_fen, generate_legal_moves, make_move, perft,
+              STARTING_FEN};
+
+fn nodes(fen: &str, depth: usize) -> usize {
+    let mut results = [-1isize; 8];
+    perft(&mut results, &fen_to_game_state(fen).unwrap(), depth)
+}
+
+#[test]
+fn starting_positions_by_index() {
+    let fen = |index| game_state_to_fen(&chess960_position(index).unwrap());
+    assert_eq!(fen(518), STARTING_FEN);
+    assert_eq!(fen(0), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1");
+    assert_eq!(fen(959), "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1");
+    assert!(chess960_position(960).is_none());
+}
+
+#[test]
+fn shredder_and_x_fen_castling() {
+    // Shredder-FEN letters become K/Q when the rook is the outermost one
+    let game_state = fen_to_game_state("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9").unwrap();
+    assert_eq!(game_state_to_fen(&game_state), "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9");
+
+    // ...and stay file letters when another rook stands further out
+    let fen = "4rrk1/pbbp2p1/1ppnp3/3n1pqp/3N1PQP/1PPNP3/PBBP2P1/4RRK1 w Ff - 0 1";
+    assert_eq!(game_state_to_fen(&fen_to_game_state(fen).unwrap()), fen);
+
+}
+
+#[test]
+fn double_fischer_random() {
+    let game_state = double_chess960_position(518, 0).unwrap();
+    assert_eq!(game_state_to_fen(&game_state), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    assert!(double_chess960_position(0, 960).is_none());
+
+    // each side keeps its own rook files
+    let fen = "1r2k1r1/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    assert_eq!(game_state_to_fen(&fen_to_game_state(fen).unwrap()), fen);
+    let fen = "rk3r2/8/8/8/8/8/8/3RK1R1 w GDfa - 0 1";
+    let game_state = fen_to_game_state(fen).unwrap();
+    assert_eq!(game_state_to_fen(&game_state), "rk3r2/8/8/8/8/8/8/3RK1R1 w KQkq - 0 1");
+    // queenside castling is legal, kingside would cross the attacked f1
+    assert_eq!(nodes(fen, 1), 22);
+}
+
+#[test]
+fn castling_is_king_takes_rook() {
+    let mut game_state = fen_to_game_state("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1").unwrap();
+    let castle = generate_legal_moves(&game_state)
+        .into_iter()
+        .find(|mv| format!("{:?}", mv) == "e1b1")
+        .unwrap();
+    make_move(&mut game_state, castle);
+    assert_eq!(game_state_to_fen(&game_state), "1r2k1r1/8/8/8/8/8/8/2KR2R1 b kq - 1 1");
+}
+
+#[test]
+fn chess960_perft() {
+    assert_eq!(nodes("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", 3), 12189);
+    assert_eq!(nodes("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9", 3), 18002);
+    // the rook moving away uncovers a check on the king's destination
+    assert_eq!(nodes("r1k1r2q/p1ppp1pp/8/8/8/8/P1PPP1PP/R1K1R2Q w KQkq - 0 1", 3), 12333);
+    assert_eq!(nodes("2r5/8/8/8/8/8/6PP/k2KR3 w K - 0 1", 3), 3931);
+    assert_eq!(nodes("4r3/3k4/8/8/8/8/6PP/qR1K1R2 w KQ - 0 1", 3), 12858);
+    assert_eq!(nodes("4rrk1/pbbp2p1/1ppnp3/3n1pqp/3N1PQP/1PPNP3/PBBP2P1/4RRK1 w Ff - 0 1", 2), 1743);
+}
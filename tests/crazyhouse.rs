@@ -0,0 +1,105 @@
+use xadreis::{compute_zobrist_key, fen_to_game_state, game_state_to_fen, make_move, unmake_move, variant_perft, Crazyhouse,
+              FenError, Game, PieceType, Player, PositionError, Standard, Variant};
+
+#[test]
+fn pockets_in_fen() {
+    let start = Crazyhouse.position_from_fen(Crazyhouse.starting_fen()).unwrap();
+    assert_eq!(game_state_to_fen(&start), Crazyhouse.starting_fen());
+    assert_eq!(start.pocket(Player::White, PieceType::Pawn), Some(0));
+
+    let fen = "r1bk3r/pp1pQ~ppp/8/8/8/8/PPP2PPP/R1B1K2R[NPPqb] b KQ - 0 12";
+    let game_state = Crazyhouse.position_from_fen(fen).unwrap();
+    assert_eq!(game_state_to_fen(&game_state), fen);
+    assert_eq!(game_state.pocket(Player::White, PieceType::Pawn), Some(2));
+    assert_eq!(game_state.pocket(Player::Black, PieceType::Queen), Some(1));
+    assert!(game_state.is_promoted(1, 4));
+
+    // the pockets may also be given as a ninth rank
+    let ninth_rank = Crazyhouse.position_from_fen("r1bk3r/pp1pQ~ppp/8/8/8/8/PPP2PPP/R1B1K2R/NPPqb b KQ - 0 12").unwrap();
+    assert_eq!(game_state_to_fen(&ninth_rank), fen);
+
+    assert!(Crazyhouse.position_from_fen("4k3/8/8/8/8/8/8/4K3[Kq] w - - 0 1").is_err());
+    assert!(Crazyhouse.position_from_fen("4k3/8/8/8/8/8/8/4K3[q w - - 0 1").is_err());
+    assert!(Crazyhouse.position_from_fen("4k3/8/8/8/8/8/8/~3K3[] w - - 0 1").is_err());
+}
+
+#[test]
+fn pockets_only_in_crazyhouse() {
+    let pockets = Err(FenError::InvalidPosition(vec![PositionError::PocketsNotAllowed]));
+    assert_eq!(fen_to_game_state("4k3/8/8/8/8/8/8/4K3[Q] w - - 0 1").map(|_| ()), pockets);
+    assert_eq!(Standard.position_from_fen("4k3/8/8/8/8/8/8/4K3/Q w - - 0 1").map(|_| ()), pockets);
+    assert_eq!(Standard.position_from_fen("4k3/8/8/8/8/8/8/3QK3[] w - - 0 1").map(|_| ()), pockets);
+    assert_eq!(Standard.position_from_fen("4k3/8/8/8/8/8/8/3Q~K3 w - - 0 1").map(|_| ()), pockets);
+}
+
+const QUEEN_AND_KNIGHT: &str = "2k5/8/8/8/8/8/8/4K3[Qn] w - - 0 1";
+const FULL_POCKETS: &str = "2k5/8/8/8/8/8/8/4K3[QRBNPqrbnp] w - - 0 1";
+const MIDDLEGAME: &str = "r1bqk2r/pppp1ppp/2n1p3/4P3/1b1Pn3/2NB1N2/PPP2PPP/R1BQK2R[] b KQkq - 0 1";
+
+fn nodes(fen: &str, depth: usize) -> usize {
+    variant_perft(&Crazyhouse, &Crazyhouse.position_from_fen(fen).unwrap(), depth)
+}
+
+#[test]
+fn crazyhouse_perft() {
+    assert_eq!(nodes(QUEEN_AND_KNIGHT, 1), 67);
+    assert_eq!(nodes(QUEEN_AND_KNIGHT, 2), 3083);
+    assert_eq!(nodes(QUEEN_AND_KNIGHT, 3), 88634);
+    assert_eq!(nodes(FULL_POCKETS, 1), 301);
+    assert_eq!(nodes(FULL_POCKETS, 2), 75353);
+    assert_eq!(nodes(MIDDLEGAME, 1), 42);
+    assert_eq!(nodes(MIDDLEGAME, 2), 1347);
+    assert_eq!(nodes(MIDDLEGAME, 3), 58057);
+}
+
+#[test]
+fn captures_and_drops() {
+    let mut game = Game::with_variant(Crazyhouse.position_from_fen(Crazyhouse.starting_fen()).unwrap(), &Crazyhouse);
+    for name in ["e2e4", "d7d5", "e4d5", "d8d5", "b1c3", "d5a5"] {
+        let mv = game.variant().parse_move(game.current(), name).unwrap();
+        game.play(mv);
+    }
+    assert_eq!(game.current().pocket(Player::White, PieceType::Pawn), Some(1));
+    assert_eq!(game.current().pocket(Player::Black, PieceType::Pawn), Some(1));
+
+    let drop = Crazyhouse.parse_move(game.current(), "P@d5").unwrap();
+    assert_eq!(format!("{:?}", drop), "P@d5");
+    assert_eq!(drop.drop_piece(), Some(PieceType::Pawn));
+    assert_eq!(drop.from(), drop.to());
+    // pawns never go on the back ranks, and drops need an empty square
+    assert_eq!(Crazyhouse.parse_move(game.current(), "P@d8"), None);
+    assert_eq!(Crazyhouse.parse_move(game.current(), "P@e7"), None);
+    assert_eq!(Crazyhouse.parse_move(game.current(), "N@f3"), None);
+
+    game.play(drop);
+    assert_eq!(game.current().piece_at(3, 3), (PieceType::Pawn, Player::White));
+    assert_eq!(game.current().pocket(Player::White, PieceType::Pawn), Some(0));
+    assert_eq!(game.current().zobrist_key(), compute_zobrist_key(game.current()));
+}
+
+#[test]
+fn promoted_pieces_return_as_pawns() {
+    let fen = "4k3/1P6/8/8/8/8/4K3/7r[] w - - 0 1";
+    let mut game_state = Crazyhouse.position_from_fen(fen).unwrap();
+    let before = game_state_to_fen(&game_state);
+
+    let promotion = Crazyhouse.parse_move(&game_state, "b7b8q").unwrap();
+    let undo_promotion = make_move(&mut game_state, promotion);
+    assert!(game_state.is_promoted(0, 1));
+    let king_move = Crazyhouse.parse_move(&game_state, "e8d7").unwrap();
+    let undo_king_move = make_move(&mut game_state, king_move);
+    let queen_move = Crazyhouse.parse_move(&game_state, "b8b1").unwrap();
+    let undo_queen_move = make_move(&mut game_state, queen_move);
+    assert!(game_state.is_promoted(7, 1));
+    let capture = Crazyhouse.parse_move(&game_state, "h1b1").unwrap();
+    let undo_capture = make_move(&mut game_state, capture);
+
+    assert_eq!(game_state_to_fen(&game_state), "8/3k4/8/8/8/8/4K3/1r6[p] w - - 0 3");
+
+    unmake_move(&mut game_state, capture, undo_capture);
+    unmake_move(&mut game_state, queen_move, undo_queen_move);
+    unmake_move(&mut game_state, king_move, undo_king_move);
+    unmake_move(&mut game_state, promotion, undo_promotion);
+    assert_eq!(game_state_to_fen(&game_state), before);
+    assert_eq!(game_state.zobrist_key(), compute_zobrist_key(&game_state));
+}
@@ -0,0 +1,107 @@
+use xadreis::{fen_to_game_state, game_state_to_fen, generate_legal_moves, make_move, FenError, FenField, GameState, Player,
+              PositionError, ThreeCheck, Variant, STARTING_FEN};
+
+fn play(game_state: &mut GameState, moves: &[&str]) {
+    for name in moves {
+        let mv = generate_legal_moves(game_state)
+            .into_iter()
+            .find(|mv| format!("{:?}", mv) == *name)
+            .unwrap_or_else(|| panic!("{} is not legal", name));
+        make_move(game_state, mv);
+    }
+}
+
+#[test]
+fn round_trip() {
+    let fens = [
+        STARTING_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "rnbqkbnr/pppp1ppp/8/8/3Pp3/8/PPP1PPPP/RNBQKBNR b Kq d3 0 3",
+    ];
+
+    for fen in fens {
+        let game_state = fen_to_game_state(fen).unwrap();
+        assert_eq!(game_state_to_fen(&game_state), fen);
+    }
+}
+
+#[test]
+fn round_trip_normalizes_whitespace() {
+    let game_state = fen_to_game_state("  8/8/8/4k3/8/8/8/4K3   b  -  -  12  40 ").unwrap();
+    assert_eq!(game_state_to_fen(&game_state), "8/8/8/4k3/8/8/8/4K3 b - - 12 40");
+}
+
+#[test]
+fn follows_played_moves() {
+    let mut game_state = fen_to_game_state(STARTING_FEN).unwrap();
+
+    play(&mut game_state, &["g1f3", "g8f6"]);
+    assert_eq!(game_state_to_fen(&game_state),
+               "rnbqkb1r/pppppppp/5n2/8/8/5N2/PPPPPPPP/RNBQKB1R w KQkq - 2 2");
+
+    play(&mut game_state, &["h1g1", "f6e4", "g1h1", "e4d2"]);
+    assert_eq!(game_state_to_fen(&game_state),
+               "rnbqkb1r/pppppppp/8/8/8/5N2/PPPnPPPP/RNBQKB1R w Qkq - 0 4");
+
+    play(&mut game_state, &["e1d2", "d7d5"]);
+    assert_eq!(game_state_to_fen(&game_state),
+               "rnbqkb1r/ppp1pppp/8/3p4/8/5N2/PPPKPPPP/RNBQ1B1R w kq d6 0 5");
+}
+
+#[test]
+fn rejected_fens() {
+    let cases = [
+        ("8/8/8/4k3/8/8/8/4K3 w - - 0",
+         FenError::WrongFieldCount(5), "expected 6 fields (7 in Three-check), found 5"),
+        ("8/8/4k3/8/8/8/4K3 w - - 0 1",
+         FenError::WrongRankCount(7), "piece placement: expected 8 ranks, found 7"),
+        ("8/8/8/4k4/8/8/8/4K3 w - - 0 1",
+         FenError::BadRankLength { rank: 5, length: 9 }, "piece placement: rank 5 covers 9 files instead of 8"),
+        ("8/8/8/4k3/8/8/8/4K2X w - - 0 1",
+         FenError::UnexpectedSymbol { field: FenField::Placement, symbol: "X".to_string() },
+         "piece placement: unexpected symbol 'X'"),
+        ("8/8/8/4k3/8/8/8/4K3 x - - 0 1",
+         FenError::UnexpectedSymbol { field: FenField::SideToMove, symbol: "x".to_string() },
+         "side to move: unexpected symbol 'x'"),
+        ("r3k2r/8/8/8/8/8/8/R3K2R w KQkx - 0 1",
+         FenError::InvalidCastling("x".to_string()), "castling rights: invalid or repeated letter 'x'"),
+        ("8/8/8/4k3/8/8/8/4K3 w - e9 0 1",
+         FenError::InvalidEnPassant("e9".to_string()), "en passant target: 'e9' is not a square"),
+        ("8/8/8/4k3/4P3/8/8/4K3 b - e4 0 1",
+         FenError::EnPassantWrongRank("e4".to_string()),
+         "en passant target: 'e4' is not on the rank behind a double-pushed pawn"),
+        ("8/8/8/4k3/8/8/8/4K3 w - - x 1",
+         FenError::InvalidCounter { field: FenField::HalfmoveClock, value: "x".to_string() },
+         "halfmove clock: 'x' is not a number"),
+        ("8/8/8/4k3/8/8/8/4K3 w - - 0 -1",
+         FenError::InvalidCounter { field: FenField::FullmoveNumber, value: "-1".to_string() },
+         "fullmove number: '-1' is not a number"),
+        ("8/8/8/8/8/8/8/4K3 w - - 0 1",
+         FenError::InvalidPosition(vec![PositionError::WrongKingCount { player: Player::Black, count: 0 }]),
+         "impossible position: Black has 0 kings instead of 1"),
+    ];
+
+    for (fen, err, message) in cases {
+        assert_eq!(fen_to_game_state(fen).unwrap_err(), err, "{}", fen);
+        assert_eq!(err.to_string(), message);
+    }
+
+    // the field is named in the message whenever there is one
+    assert_eq!(FenError::WrongFieldCount(5).field(), None);
+    assert_eq!(FenError::InvalidPosition(vec![]).field(), None);
+    assert_eq!(FenError::WrongRankCount(7).field(), Some(FenField::Placement));
+    assert_eq!(FenError::BadRankLength { rank: 5, length: 9 }.field(), Some(FenField::Placement));
+    assert_eq!(FenError::InvalidCastling("x".to_string()).field(), Some(FenField::Castling));
+    assert_eq!(FenError::InvalidEnPassant("e9".to_string()).field(), Some(FenField::EnPassant));
+    assert_eq!(FenError::EnPassantWrongRank("e4".to_string()).field(), Some(FenField::EnPassant));
+    let counter = FenError::InvalidCounter { field: FenField::HalfmoveClock, value: "x".to_string() };
+    assert_eq!(counter.field(), Some(FenField::HalfmoveClock));
+
+    let err = ThreeCheck.position_from_fen(&format!("{} +4+0", STARTING_FEN)).unwrap_err();
+    assert_eq!(err.field(), Some(FenField::CheckCount));
+    assert_eq!(err.to_string(), "check count: '+4+0' is not +N+M with N and M from 0 to 3");
+}
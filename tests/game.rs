@@ -0,0 +1,101 @@
+use xadreis::{game_state_to_fen, Atomic, DrawReason, Game, GameResult, Variant, STARTING_FEN};
+
+fn play_names(game: &mut Game, names: &[&str]) {
+    for name in names {
+        let mv = game.variant().parse_move(game.current(), name).unwrap();
+        game.play(mv);
+    }
+}
+
+const KNIGHT_DANCE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+#[test]
+fn repetition() {
+    let mut game = Game::from_fen(STARTING_FEN).unwrap();
+    play_names(&mut game, &KNIGHT_DANCE);
+    assert_eq!(game.repetition_count(), 2);
+    assert!(!game.is_threefold_repetition());
+    assert_eq!(game.claimable_draw(), None);
+
+    // threefold can be claimed, but the game goes on
+    play_names(&mut game, &KNIGHT_DANCE);
+    assert!(game.is_threefold_repetition());
+    assert_eq!(game.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+    assert_eq!(game.result(), None);
+
+    // fivefold ends the game, leaving nothing to claim
+    play_names(&mut game, &KNIGHT_DANCE);
+    assert_eq!(game.repetition_count(), 4);
+    assert_eq!(game.result(), None);
+    play_names(&mut game, &KNIGHT_DANCE);
+    assert_eq!(game.repetition_count(), 5);
+    assert!(game.is_fivefold_repetition());
+    assert_eq!(game.result(), Some(GameResult::Draw { reason: DrawReason::FivefoldRepetition }));
+    assert_eq!(game.claimable_draw(), None);
+}
+
+#[test]
+fn undo() {
+    let mut game = Game::from_fen(STARTING_FEN).unwrap();
+    assert_eq!(game.undo(), None);
+
+    play_names(&mut game, &KNIGHT_DANCE);
+    play_names(&mut game, &KNIGHT_DANCE);
+    assert!(game.is_threefold_repetition());
+
+    let last = *game.moves().last().unwrap();
+    assert_eq!(game.undo(), Some(last));
+    assert_eq!(format!("{:?}", last), "f6g8");
+    assert_eq!(game.moves().len(), 7);
+    assert_eq!(game.positions().len(), 8);
+    assert_eq!(game.repetition_count(), 2);
+    assert_eq!(game.claimable_draw(), None);
+
+    while game.undo().is_some() {}
+    assert!(game.moves().is_empty());
+    assert_eq!(game_state_to_fen(game.current()), STARTING_FEN);
+}
+
+#[test]
+fn castling_rights_change_the_position() {
+    let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let rook_dance = ["h1g1", "h8g8", "g1h1", "g8h8"];
+
+    // the pieces are back, but the kingside castling rights are gone
+    play_names(&mut game, &rook_dance);
+    assert_eq!(game_state_to_fen(game.current()), "r3k2r/8/8/8/8/8/8/R3K2R w Qq - 4 3");
+    assert_eq!(game.repetition_count(), 1);
+
+    play_names(&mut game, &rook_dance);
+    assert_eq!(game.repetition_count(), 2);
+}
+
+#[test]
+fn en_passant_changes_the_position() {
+    let king_dance = ["e8d8", "e1d1", "d8e8", "d1e1"];
+
+    // after e2e4 the f4 pawn may take en passant, which it can't later on
+    let mut game = Game::from_fen("4k3/8/8/8/5p2/8/4P3/4K3 w - - 0 1").unwrap();
+    play_names(&mut game, &["e2e4"]);
+    play_names(&mut game, &king_dance);
+    assert_eq!(game.repetition_count(), 1);
+    play_names(&mut game, &king_dance);
+    assert_eq!(game.repetition_count(), 2);
+
+    // a double push with no pawn to take it repeats like any other move
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    play_names(&mut game, &["e2e4"]);
+    play_names(&mut game, &king_dance);
+    assert_eq!(game.repetition_count(), 2);
+
+    // and so does one where taking en passant would expose the king
+    let mut game = Game::from_fen("8/8/8/8/k4p1R/8/4P3/4K3 w - - 0 1").unwrap();
+    play_names(&mut game, &["e2e4", "a4a5", "e1d1", "a5a4", "d1e1"]);
+    assert_eq!(game.repetition_count(), 2);
+
+    // or where the variant forbids it: in Atomic the blast would take Black's king
+    let start = Atomic.position_from_fen("8/8/8/8/3p4/8/3kP3/7K w - - 0 1").unwrap();
+    let mut game = Game::with_variant(start, &Atomic);
+    play_names(&mut game, &["e2e4", "d2c2", "h1g1", "c2d2", "g1h1"]);
+    assert_eq!(game.repetition_count(), 2);
+}
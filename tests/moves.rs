@@ -0,0 +1,61 @@
+use xadreis::{fen_to_game_state, game_state_to_fen, generate_legal_moves, make_move, unmake_move, Crazyhouse, MoveKind,
+              Standard, Variant};
+
+#[test]
+fn move_kinds() {
+    let game_state = Standard.position_from_fen("r3k2r/1P6/8/3pP3/8/8/7P/R3K2R w KQkq d6 0 1").unwrap();
+    let kind = |name: &str| Standard.parse_move(&game_state, name).unwrap().kind();
+    assert_eq!(kind("h2h3"), MoveKind::Quiet);
+    assert_eq!(kind("h2h4"), MoveKind::DoublePush);
+    assert_eq!(kind("a1a8"), MoveKind::Capture);
+    assert_eq!(kind("e5d6"), MoveKind::EnPassant);
+    assert_eq!(kind("e1g1"), MoveKind::Castling);
+    assert_eq!(kind("e1c1"), MoveKind::Castling);
+    assert_eq!(kind("b7b8q"), MoveKind::Promotion);
+    assert_eq!(kind("b7a8n"), MoveKind::CapturePromotion);
+
+    let captures: Vec<_> = Standard.legal_moves(&game_state).iter()
+        .filter(|mv| mv.is_capture())
+        .map(|mv| format!("{:?}", mv))
+        .collect();
+    assert_eq!(captures.len(), 6, "{:?}", captures);
+
+    // castling is written as the king's move unless it is Chess960 castling
+    let standard = Standard.position_from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    assert!(Standard.parse_move(&standard, "e1g1").is_some());
+    assert!(Standard.parse_move(&standard, "e1h1").is_none());
+    let chess960 = Standard.position_from_fen("4k3/8/8/8/8/8/8/5K1R w H - 0 1").unwrap();
+    assert!(Standard.parse_move(&chess960, "f1h1").is_some());
+    assert!(Standard.parse_move(&chess960, "f1g1").is_some_and(|mv| mv.kind() == MoveKind::Quiet));
+
+    let pockets = Crazyhouse.position_from_fen("4k3/8/8/8/8/8/8/4K3[N] w - - 0 1").unwrap();
+    let drop = Crazyhouse.parse_move(&pockets, "N@f3").unwrap();
+    assert_eq!(drop.kind(), MoveKind::Drop);
+    assert!(!drop.is_capture());
+}
+
+#[test]
+fn unmake_restores_the_position() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 5 20",
+        "r3k2r/1P6/8/3pP3/8/8/7P/R3K2R w KQkq d6 0 1",
+    ];
+    let mut kinds = vec![];
+    for fen in fens {
+        let mut game_state = fen_to_game_state(fen).unwrap();
+        let key = game_state.zobrist_key();
+        for mv in generate_legal_moves(&game_state) {
+            let undo = make_move(&mut game_state, mv);
+            unmake_move(&mut game_state, mv, undo);
+            assert_eq!(game_state_to_fen(&game_state), fen, "{:?}", mv);
+            assert_eq!(game_state.zobrist_key(), key, "{:?}", mv);
+            kinds.push(mv.kind());
+        }
+    }
+
+    for kind in [MoveKind::EnPassant, MoveKind::Castling, MoveKind::Promotion, MoveKind::CapturePromotion] {
+        assert!(kinds.contains(&kind), "{:?}", kind);
+    }
+}
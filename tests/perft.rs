@@ -0,0 +1,37 @@
+use xadreis::{fen_to_game_state, perft, STARTING_FEN};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn nodes(fen: &str, depth: usize) -> usize {
+    let mut results = [-1isize; 8];
+    perft(&mut results, &fen_to_game_state(fen).unwrap(), depth)
+}
+
+#[test]
+fn standard_positions() {
+    assert_eq!(nodes(STARTING_FEN, 3), 8902);
+    assert_eq!(nodes(KIWIPETE, 2), 2039);
+    assert_eq!(nodes(POSITION_3, 4), 43238);
+    assert_eq!(nodes(POSITION_4, 3), 9467);
+    assert_eq!(nodes(POSITION_5, 2), 1486);
+    assert_eq!(nodes(POSITION_6, 2), 2079);
+}
+
+// checks, double checks, pins and en passant captures that expose the king
+#[test]
+fn check_and_pin_edge_cases() {
+    assert_eq!(nodes("8/8/8/5k2/3p4/8/4P3/4K3 w - - 0 1", 4), 2810);
+    assert_eq!(nodes("8/8/8/B2p3Q/2qPp1P1/b7/2P2PkP/4K2R b K - 0 1", 3), 14583);
+    assert_eq!(nodes("3R4/8/q4k2/2B5/1NK5/3b4/8/8 w - - 0 1", 3), 2854);
+    assert_eq!(nodes("2Nq4/2K5/1b6/8/7R/3k4/7P/8 w - - 0 1", 3), 1217);
+    assert_eq!(nodes("5R2/2P5/8/4k3/8/3rK2r/8/8 w - - 0 1", 3), 1030);
+    assert_eq!(nodes("8/8/8/1k6/3Pp3/8/8/4KQ2 b - d3 0 1", 3), 711);
+    assert_eq!(nodes("1b1k4/8/8/1rPpK3/8/8/8/8 w - d6 0 1", 3), 555);
+    assert_eq!(nodes("rnbqk1nr/bb3p1p/1q2r3/2pPp3/3P4/7P/1PP1NpPP/R1BQKBNR w KQkq c6 0 1", 3), 2528);
+    assert_eq!(nodes("1rrrrrk1/1PPPPPPP/8/8/8/8/8/6K1 b - - 0 1", 3), 1919);
+    assert_eq!(nodes("1q4k1/3r1Ppp/5NP1/pP6/8/1Q6/3B4/2K2R2 b - - 0 1", 3), 2826);
+}
@@ -0,0 +1,43 @@
+use xadreis::{fen_to_game_state, game_state_to_fen, generate_legal_moves, make_move, reference, unmake_move, GameState,
+              Move, STARTING_FEN};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn sorted_names(moves: &[Move]) -> Vec<String> {
+    let mut names: Vec<_> = moves.iter().map(|mv| format!("{:?} {:?}", mv, mv.kind())).collect();
+    names.sort();
+    names
+}
+
+// the bitboard generator must agree with the mailbox one in every node
+fn compare_generators(game_state: &mut GameState, depth: usize) {
+    let moves = generate_legal_moves(game_state);
+    assert_eq!(sorted_names(&moves), sorted_names(&reference::generate_legal_moves(game_state)),
+               "{}", game_state_to_fen(game_state));
+
+    if depth == 0 {
+        return;
+    }
+    for mv in moves {
+        let undo = make_move(game_state, mv);
+        compare_generators(game_state, depth - 1);
+        unmake_move(game_state, mv, undo);
+    }
+}
+
+#[test]
+fn matches_reference_generator() {
+    for fen in [STARTING_FEN, KIWIPETE, POSITION_3, POSITION_4, POSITION_5, POSITION_6] {
+        compare_generators(&mut fen_to_game_state(fen).unwrap(), 1);
+    }
+}
+
+#[test]
+fn reference_perft() {
+    let game_state = fen_to_game_state(KIWIPETE).unwrap();
+    assert_eq!(reference::perft(&game_state, 2), 2039);
+}
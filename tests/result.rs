@@ -0,0 +1,125 @@
+use xadreis::{fen_to_game_state, game_result, has_insufficient_material, is_check, is_fifty_move_draw,
+              is_insufficient_material, is_seventy_five_move_draw, position_status, timeout_result, DrawReason, Game,
+              GameResult, Player, PositionStatus, WinReason};
+
+fn play_names(game: &mut Game, names: &[&str]) {
+    for name in names {
+        let mv = game.variant().parse_move(game.current(), name).unwrap();
+        game.play(mv);
+    }
+}
+
+#[test]
+fn checkmate_and_stalemate() {
+    // back rank mate, and fool's mate
+    for (fen, winner) in [
+        ("3R2k1/5ppp/8/8/8/8/8/6K1 b - - 1 1", Player::White),
+        ("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3", Player::Black),
+    ] {
+        let game_state = fen_to_game_state(fen).unwrap();
+        assert!(is_check(&game_state));
+        assert_eq!(position_status(&game_state), PositionStatus::Checkmate { winner });
+        assert_eq!(game_result(&game_state), Some(GameResult::Win { winner, reason: WinReason::Checkmate }));
+    }
+
+    let stalemate = fen_to_game_state("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(!is_check(&stalemate));
+    assert_eq!(position_status(&stalemate), PositionStatus::Stalemate);
+    assert_eq!(game_result(&stalemate), Some(GameResult::Draw { reason: DrawReason::Stalemate }));
+
+    // in check, but the king can get away
+    let check = fen_to_game_state("4k3/8/8/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+    assert!(is_check(&check));
+    assert_eq!(position_status(&check), PositionStatus::Ongoing);
+    assert_eq!(game_result(&check), None);
+}
+
+#[test]
+fn fifty_move_rule() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 99 60").unwrap();
+    assert!(!is_fifty_move_draw(game.current()));
+
+    // a pawn move starts the count again
+    play_names(&mut game, &["e2e3"]);
+    assert_eq!(game.current().halfmove_counter(), 0);
+    game.undo();
+
+    // the hundredth quiet move lets either side claim, but doesn't end the game
+    play_names(&mut game, &["a1a2"]);
+    assert!(is_fifty_move_draw(game.current()));
+    assert!(!is_seventy_five_move_draw(game.current()));
+    assert_eq!(game.claimable_draw(), Some(DrawReason::FiftyMoveRule));
+    assert_eq!(game.result(), None);
+}
+
+#[test]
+fn seventy_five_move_rule() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/R3K3 w - - 149 85").unwrap();
+    assert_eq!(game.result(), None);
+
+    play_names(&mut game, &["a1a2"]);
+    assert!(is_seventy_five_move_draw(game.current()));
+    assert_eq!(game.result(), Some(GameResult::Draw { reason: DrawReason::SeventyFiveMoveRule }));
+    assert_eq!(game_result(game.current()), game.result());
+    assert_eq!(game.claimable_draw(), None);
+}
+
+#[test]
+fn checkmate_beats_the_move_rules() {
+    let mated = GameResult::Win { winner: Player::White, reason: WinReason::Checkmate };
+    for fen in ["7k/8/6K1/8/8/8/8/R7 w - - 99 80", "7k/8/6K1/8/8/8/8/R7 w - - 149 80"] {
+        let mut game = Game::from_fen(fen).unwrap();
+        play_names(&mut game, &["a1a8"]);
+        assert!(is_fifty_move_draw(game.current()));
+        assert_eq!(game.result(), Some(mated));
+        assert_eq!(game.claimable_draw(), None);
+    }
+}
+
+#[test]
+fn insufficient_material() {
+    let dead = Some(GameResult::Draw { reason: DrawReason::InsufficientMaterial });
+    for fen in [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4KN2 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        // both bishops on dark squares
+        "4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1",
+    ] {
+        let game_state = fen_to_game_state(fen).unwrap();
+        assert!(is_insufficient_material(&game_state), "{}", fen);
+        assert_eq!(game_result(&game_state), dead, "{}", fen);
+    }
+
+    // with bishops on opposite colours, or a knight each, mate can be helped along
+    for fen in ["2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1", "4kn2/8/8/8/8/8/8/4KN2 w - - 0 1"] {
+        let game_state = fen_to_game_state(fen).unwrap();
+        assert!(!has_insufficient_material(&game_state, Player::White), "{}", fen);
+        assert!(!has_insufficient_material(&game_state, Player::Black), "{}", fen);
+        assert_eq!(game_result(&game_state), None, "{}", fen);
+    }
+
+    // a knight can't make the queen block her own king in
+    let queen = fen_to_game_state("q3k3/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    assert!(has_insufficient_material(&queen, Player::White));
+    assert!(!has_insufficient_material(&queen, Player::Black));
+    assert!(!is_insufficient_material(&queen));
+}
+
+#[test]
+fn timeout() {
+    let draw = GameResult::Draw { reason: DrawReason::InsufficientMaterial };
+    let win = |winner| GameResult::Win { winner, reason: WinReason::Timeout };
+
+    let kings = fen_to_game_state("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(timeout_result(&kings, Player::White), draw);
+
+    // only the flag's opponent needs mating material
+    let queen = fen_to_game_state("q3k3/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    assert_eq!(timeout_result(&queen, Player::White), win(Player::Black));
+    assert_eq!(timeout_result(&queen, Player::Black), draw);
+
+    let knights = fen_to_game_state("4kn2/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap();
+    assert_eq!(timeout_result(&knights, Player::White), win(Player::Black));
+    assert_eq!(timeout_result(&knights, Player::Black), win(Player::White));
+}
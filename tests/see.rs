@@ -0,0 +1,34 @@
+use xadreis::{fen_to_game_state, generate_legal_moves, see};
+
+fn see_of(fen: &str, name: &str) -> i32 {
+    let game_state = fen_to_game_state(fen).unwrap();
+    let mv = generate_legal_moves(&game_state)
+        .into_iter()
+        .find(|mv| format!("{:?}", mv) == name)
+        .unwrap_or_else(|| panic!("{} is not legal", name));
+    see(&game_state, mv)
+}
+
+#[test]
+fn winning_and_losing_captures() {
+    // undefended pawn
+    assert_eq!(see_of("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "e1e5"), 100);
+    // pawn defended by a knight, bishop and rook with the queen behind
+    assert_eq!(see_of("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1", "d3e5"), -200);
+    // queen takes a pawn defended by a pawn
+    assert_eq!(see_of("4k3/8/3p4/4p3/8/8/8/4QK2 w - - 0 1", "e1e5"), -800);
+}
+
+#[test]
+fn xrays_and_special_moves() {
+    // doubled rooks win a rook defended once
+    assert_eq!(see_of("3r3k/3r4/8/8/8/8/3R4/3RK3 w - - 0 1", "d2d7"), 500);
+    assert_eq!(see_of("3rk3/3r4/8/8/8/8/3R4/3RK3 w - - 0 1", "d2d7"), 0);
+    // the king may only recapture when nothing x-rays the square
+    assert_eq!(see_of("3k4/3n4/8/8/8/8/3Q4/3R2K1 w - - 0 1", "d2d7"), 300);
+    assert_eq!(see_of("3k4/3n4/8/8/8/8/3Q4/6K1 w - - 0 1", "d2d7"), -600);
+    // en passant wins the pawn
+    assert_eq!(see_of("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+    // promoting on an undefended square wins a queen for a pawn
+    assert_eq!(see_of("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q"), 800);
+}
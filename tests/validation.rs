@@ -0,0 +1,51 @@
+use xadreis::{compute_zobrist_key, fen_to_game_state, game_state_to_fen, generate_legal_moves, validate_position,
+              CastlingSide, FenError, GameState, PieceType, Player, PositionError};
+
+#[test]
+fn every_error_is_reported() {
+    let empty = GameState::empty();
+    assert_eq!(validate_position(&empty), Err(vec![
+        PositionError::WrongKingCount { player: Player::White, count: 0 },
+        PositionError::WrongKingCount { player: Player::Black, count: 0 },
+    ]));
+
+    // nine pawns, one of them on the back rank, and rights without a rook
+    let fen = "r3k3/8/8/8/8/8/PPPPPPPP/P3K2R w Kk - 0 1";
+    let errors = match fen_to_game_state(fen) {
+        Err(FenError::InvalidPosition(errors)) => errors,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(errors, vec![
+        PositionError::TooManyPawns { player: Player::White, count: 9 },
+        PositionError::PawnOnBackRank { square: (7, 0) },
+        PositionError::CastlingWithoutPieces { player: Player::Black, side: CastlingSide::Kingside },
+    ]);
+}
+
+#[test]
+fn editing_a_position() {
+    let mut game_state = GameState::empty();
+    game_state.set_piece_at(7, 4, PieceType::King, Player::White);
+    game_state.set_piece_at(0, 4, PieceType::King, Player::Black);
+    game_state.set_piece_at(6, 4, PieceType::Pawn, Player::White);
+    assert_eq!(validate_position(&game_state), Ok(()));
+    assert_eq!(game_state_to_fen(&game_state), "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+    assert_eq!(game_state.zobrist_key(), compute_zobrist_key(&game_state));
+    assert_eq!(generate_legal_moves(&game_state).len(), 6);
+
+    // a rook giving check is fine for the side to move, but not for the other
+    game_state.set_piece_at(0, 0, PieceType::Rook, Player::White);
+    game_state.set_piece_at(6, 4, PieceType::None, Player::None);
+    game_state.set_piece_at(1, 4, PieceType::Pawn, Player::Black);
+    game_state.set_player_to_move(Player::Black);
+    assert_eq!(validate_position(&game_state), Ok(()));
+    game_state.set_player_to_move(Player::White);
+    assert_eq!(validate_position(&game_state), Err(vec![PositionError::OpponentInCheck { player: Player::Black }]));
+    assert_eq!(game_state.zobrist_key(), compute_zobrist_key(&game_state));
+
+    // en passant belongs to the side that was to move
+    let mut pushed = fen_to_game_state("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+    pushed.set_player_to_move(Player::White);
+    assert_eq!(game_state_to_fen(&pushed), "4k3/8/8/8/3pP3/8/8/4K3 w - - 0 1");
+    assert_eq!(pushed.zobrist_key(), compute_zobrist_key(&pushed));
+}
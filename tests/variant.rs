@@ -0,0 +1,188 @@
+use xadreis::{compute_zobrist_key, fen_to_game_state, game_state_to_fen, perft, variant_perft, DrawReason, FenError, Game,
+              GameResult, Horde, KingOfTheHill, Player, RacingKings, Standard, ThreeCheck, Variant, WinReason,
+              STARTING_FEN};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+#[test]
+fn standard_matches_plain_rules() {
+    let start = Standard.position_from_fen(Standard.starting_fen()).unwrap();
+    assert_eq!(game_state_to_fen(&start), STARTING_FEN);
+    assert_eq!(variant_perft(&Standard, &start, 3), 8902);
+
+    let kiwipete = fen_to_game_state(KIWIPETE).unwrap();
+    let mut results = [-1isize; 8];
+    assert_eq!(variant_perft(&Standard, &kiwipete, 3), perft(&mut results, &kiwipete, 3));
+}
+
+#[test]
+fn game_follows_its_variant() {
+    // fool's mate
+    let mut game = Game::with_variant(fen_to_game_state(STARTING_FEN).unwrap(), &Standard);
+    for name in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+        let mv = *game.legal_moves().iter().find(|mv| format!("{:?}", mv) == name).unwrap();
+        game.play(mv);
+    }
+    assert_eq!(game.variant().name(), "standard");
+    assert!(game.legal_moves().is_empty());
+    assert_eq!(game.result(), Standard.game_result(game.current()));
+    assert_eq!(game.result().unwrap().to_string(), "0-1");
+}
+
+fn play_names(game: &mut Game, names: &[&str]) {
+    for name in names {
+        let mv = *game.legal_moves().iter().find(|mv| format!("{:?}", mv) == *name).unwrap();
+        game.play(mv);
+    }
+}
+
+#[test]
+fn king_of_the_hill() {
+    let start = KingOfTheHill.position_from_fen(KingOfTheHill.starting_fen()).unwrap();
+    assert_eq!(variant_perft(&KingOfTheHill, &start, 4), 197281);
+
+    // positions from the standard perft suites, with their standard counts;
+    // games end sooner once a king can reach the centre
+    let evasion = KingOfTheHill.position_from_fen("8/8/8/5k2/3p4/8/4P3/4K3 w - - 0 1").unwrap();
+    assert_eq!(variant_perft(&Standard, &evasion, 4), 2810);
+    assert_eq!(variant_perft(&KingOfTheHill, &evasion, 2), 54);
+    assert_eq!(variant_perft(&KingOfTheHill, &evasion, 3), 272);
+    assert_eq!(variant_perft(&KingOfTheHill, &evasion, 4), 2319);
+    let fen = "8/6b1/r2n2Bp/P1k1p1p1/6P1/2PPP2P/1R2b2N/B3K3 b - - 0 1";
+    let random = KingOfTheHill.position_from_fen(fen).unwrap();
+    assert_eq!(variant_perft(&Standard, &random, 3), 15951);
+    assert_eq!(variant_perft(&KingOfTheHill, &random, 2), 634);
+    assert_eq!(variant_perft(&KingOfTheHill, &random, 3), 15400);
+    assert_eq!(variant_perft(&KingOfTheHill, &random, 4), 388024);
+
+    // the two moves onto the hill end the game, leaving six with replies
+    let kings = KingOfTheHill.position_from_fen("8/8/8/8/8/4K3/8/k7 w - - 0 1").unwrap();
+    assert_eq!(variant_perft(&KingOfTheHill, &kings, 1), 8);
+    assert_eq!(variant_perft(&KingOfTheHill, &kings, 2), 18);
+    assert_eq!(KingOfTheHill.game_result(&kings), None);
+
+    let mut game = Game::with_variant(kings, &KingOfTheHill);
+    play_names(&mut game, &["e3e4"]);
+    assert_eq!(game.result(), Some(GameResult::Win { winner: Player::White, reason: WinReason::KingOfTheHill }));
+}
+
+#[test]
+fn three_check_fen() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1 +2+1";
+    let game_state = ThreeCheck.position_from_fen(fen).unwrap();
+    assert_eq!(game_state.checks_given(Player::White), Some(2));
+    assert_eq!(game_state.checks_given(Player::Black), Some(1));
+    assert_eq!(game_state_to_fen(&game_state), fen);
+
+    // counts default to zero and stay out of standard FENs
+    let plain = ThreeCheck.position_from_fen(STARTING_FEN).unwrap();
+    assert_eq!(game_state_to_fen(&plain), ThreeCheck.starting_fen());
+    assert_eq!(fen_to_game_state(STARTING_FEN).unwrap().checks_given(Player::White), None);
+
+    for bad in ["+1", "+4+0", "1+1", "+a+0", "+1+1+1"] {
+        let err = ThreeCheck.position_from_fen(&format!("{} {}", STARTING_FEN, bad)).unwrap_err();
+        assert_eq!(err, FenError::InvalidCheckCount(bad.to_string()));
+    }
+
+    // only Three-check has a seventh field
+    let counted = format!("{} +3+0", STARTING_FEN);
+    assert_eq!(Standard.position_from_fen(&counted).unwrap_err(), FenError::WrongFieldCount(7));
+    assert_eq!(KingOfTheHill.position_from_fen(&counted).unwrap_err(), FenError::WrongFieldCount(7));
+    assert_eq!(fen_to_game_state(&format!("{} extra", STARTING_FEN)).unwrap_err(), FenError::WrongFieldCount(7));
+}
+
+#[test]
+fn three_check_perft() {
+    // the published Three-check suite, where these are written with the
+    // checks left rather than given, as 1+1: one check left for each side,
+    // so a third check ends the game
+    let kiwipete = ThreeCheck.position_from_fen(&format!("{} +2+2", KIWIPETE)).unwrap();
+    assert_eq!(variant_perft(&ThreeCheck, &kiwipete, 1), 48);
+    assert_eq!(variant_perft(&ThreeCheck, &kiwipete, 2), 2039);
+    assert_eq!(variant_perft(&ThreeCheck, &kiwipete, 3), 97848);
+    assert_eq!(variant_perft(&Standard, &kiwipete, 3), 97862);
+
+    let castling = ThreeCheck.position_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1 +2+2").unwrap();
+    assert_eq!(variant_perft(&ThreeCheck, &castling, 1), 26);
+    assert_eq!(variant_perft(&ThreeCheck, &castling, 2), 562);
+    assert_eq!(variant_perft(&ThreeCheck, &castling, 3), 13410);
+    assert_eq!(variant_perft(&Standard, &castling, 3), 13744);
+
+    let start = ThreeCheck.position_from_fen(ThreeCheck.starting_fen()).unwrap();
+    assert_eq!(variant_perft(&ThreeCheck, &start, 4), 197281);
+}
+
+#[test]
+fn three_check_game() {
+    let start = ThreeCheck.position_from_fen(ThreeCheck.starting_fen()).unwrap();
+    let mut game = Game::with_variant(start, &ThreeCheck);
+    play_names(&mut game, &["e2e4", "e7e5", "f1b5", "g8f6", "b5d7", "e8d7", "d1g4"]);
+    assert_eq!(game.current().checks_given(Player::White), Some(2));
+    assert_eq!(game.current().zobrist_key(), compute_zobrist_key(game.current()));
+    assert_eq!(game.result(), None);
+
+    play_names(&mut game, &["d7e8", "g4d7"]);
+    assert_eq!(game.current().checks_given(Player::White), Some(3));
+    assert!(game.legal_moves().is_empty());
+    assert_eq!(game.result(), Some(GameResult::Win { winner: Player::White, reason: WinReason::ThirdCheck }));
+}
+
+#[test]
+fn horde() {
+    let start = Horde.position_from_fen(Horde.starting_fen()).unwrap();
+    assert_eq!(variant_perft(&Horde, &start, 1), 8);
+    assert_eq!(variant_perft(&Horde, &start, 2), 128);
+    assert_eq!(variant_perft(&Horde, &start, 3), 1274);
+    assert_eq!(variant_perft(&Horde, &start, 4), 23310);
+
+    // a pawn on the first rank may double-step, but can't be taken en passant
+    let mut first_rank = Horde.position_from_fen("4k3/8/8/8/8/8/8/1P6 w - - 0 1").unwrap();
+    assert_eq!(Horde.legal_moves(&first_rank).len(), 2);
+    let mv = Horde.parse_move(&first_rank, "b1b3").unwrap();
+    Horde.play(&mut first_rank, mv);
+    assert_eq!(game_state_to_fen(&first_rank), "4k3/8/8/8/8/1P6/8/8 b - - 0 1");
+
+    let last_pawn = Horde.position_from_fen("4k3/8/8/8/8/8/1p6/P7 b - - 0 1").unwrap();
+    let mut game = Game::with_variant(last_pawn, &Horde);
+    play_names(&mut game, &["b2a1q"]);
+    assert_eq!(game.result(), Some(GameResult::Win { winner: Player::Black, reason: WinReason::HordeCaptured }));
+
+    // the side with a king plays by the usual rules
+    assert!(Horde.position_from_fen("4k3/8/8/8/8/8/8/p3P3 w - - 0 1").is_err());
+}
+
+#[test]
+fn racing_kings() {
+    let start = RacingKings.position_from_fen(RacingKings.starting_fen()).unwrap();
+    assert_eq!(variant_perft(&RacingKings, &start, 1), 21);
+    assert_eq!(variant_perft(&RacingKings, &start, 2), 421);
+    assert_eq!(variant_perft(&RacingKings, &start, 3), 11264);
+
+    let kings = RacingKings.position_from_fen("4brn1/2K2k2/8/8/8/8/8/8 w - - 0 1").unwrap();
+    assert_eq!(variant_perft(&RacingKings, &kings, 1), 6);
+    assert_eq!(variant_perft(&RacingKings, &kings, 2), 33);
+    assert_eq!(variant_perft(&RacingKings, &kings, 3), 178);
+    assert_eq!(variant_perft(&RacingKings, &kings, 4), 3151);
+
+    // no move may give check
+    let rook = RacingKings.position_from_fen("8/8/8/8/8/8/k7/6RK w - - 0 1").unwrap();
+    assert!(RacingKings.parse_move(&rook, "g1a1").is_none());
+    assert!(RacingKings.parse_move(&rook, "g1g2").is_none());
+    assert!(RacingKings.parse_move(&rook, "g1g3").is_some());
+    assert!(RacingKings.position_from_fen("8/8/8/8/8/8/k5R1/7K b - - 0 1").is_err());
+}
+
+#[test]
+fn racing_kings_finish() {
+    // Black is too far behind to catch up
+    let behind = RacingKings.position_from_fen("K7/8/6k1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert!(RacingKings.legal_moves(&behind).is_empty());
+    let white_won = GameResult::Win { winner: Player::White, reason: WinReason::ReachedEighthRank };
+    assert_eq!(RacingKings.game_result(&behind), Some(white_won));
+
+    let close = RacingKings.position_from_fen("K7/6k1/8/8/8/8/8/8 b - - 0 1").unwrap();
+    let mut game = Game::with_variant(close, &RacingKings);
+    assert_eq!(game.result(), None);
+    play_names(&mut game, &["g7g8"]);
+    assert_eq!(game.result(), Some(GameResult::Draw { reason: DrawReason::BothReachedEighthRank }));
+}
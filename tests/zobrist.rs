@@ -0,0 +1,64 @@
+use xadreis::{compute_zobrist_key, fen_to_game_state, generate_legal_moves, make_move, GameState, STARTING_FEN};
+
+fn play(game_state: &mut GameState, moves: &[&str]) {
+    for name in moves {
+        let mv = generate_legal_moves(game_state).into_iter().find(|mv| format!("{:?}", mv) == *name).unwrap();
+        make_move(game_state, mv);
+    }
+}
+
+fn key(fen: &str) -> u64 {
+    fen_to_game_state(fen).unwrap().zobrist_key()
+}
+
+#[test]
+fn transpositions_hash_alike() {
+    let start = fen_to_game_state(STARTING_FEN).unwrap();
+    let mut knights = start;
+    play(&mut knights, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(knights.zobrist_key(), start.zobrist_key());
+
+    let mut one_way = start;
+    play(&mut one_way, &["e2e3", "e7e6", "d2d3"]);
+    let mut other_way = start;
+    play(&mut other_way, &["d2d3", "e7e6", "e2e3"]);
+    assert_eq!(one_way.zobrist_key(), other_way.zobrist_key());
+}
+
+#[test]
+fn castling_and_en_passant_change_the_key() {
+    let rooks = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    assert_ne!(key(rooks), key("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1"));
+    assert_ne!(key(rooks), key("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1"));
+
+    // the same placement again, without the kingside right
+    let mut game_state = fen_to_game_state(rooks).unwrap();
+    play(&mut game_state, &["h1g1", "a8b8", "g1h1", "b8a8"]);
+    assert_ne!(game_state.zobrist_key(), key(rooks));
+    assert_eq!(game_state.zobrist_key(), key("r3k2r/8/8/8/8/8/8/R3K2R w Qk - 0 1"));
+
+    let capturable = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3";
+    assert_ne!(key(capturable), key("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3"));
+    let mut pushed = fen_to_game_state("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2").unwrap();
+    play(&mut pushed, &["f7f5"]);
+    assert_eq!(pushed.zobrist_key(), key(capturable));
+}
+
+#[test]
+fn make_move_matches_a_full_recompute() {
+    let fens = [
+        STARTING_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/1P6/8/3pP3/8/8/7P/R3K2R w KQkq d6 0 1",
+        "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w KQkq - 0 1",
+    ];
+    for fen in fens {
+        let game_state = fen_to_game_state(fen).unwrap();
+        assert_eq!(game_state.zobrist_key(), compute_zobrist_key(&game_state));
+        for mv in generate_legal_moves(&game_state) {
+            let mut after = game_state;
+            make_move(&mut after, mv);
+            assert_eq!(after.zobrist_key(), compute_zobrist_key(&after), "{} {:?}", fen, mv);
+        }
+    }
+}